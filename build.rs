@@ -0,0 +1,29 @@
+//! Selects the backend used to gather network interfaces on the target
+//! platform, exposed to the crate as one of the following cfgs:
+//!
+//! - `netlink_backend` on Linux with the `netlink` feature, unless the
+//!   `linux-getifaddrs` feature is enabled
+//! - `getifaddrs_backend` on BSD-based systems, macOS, Android, iOS, AIX,
+//!   illumos and Solaris, as well as on Linux when Netlink is not used
+//!
+//! Neither cfg is set on Windows, which has its own backend, nor on
+//! unsupported platforms.
+
+use std::env;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(netlink_backend)");
+    println!("cargo:rustc-check-cfg=cfg(getifaddrs_backend)");
+
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let netlink = env::var_os("CARGO_FEATURE_NETLINK").is_some()
+        && env::var_os("CARGO_FEATURE_LINUX_GETIFADDRS").is_none();
+
+    match target_os.as_str() {
+        "linux" if netlink => println!("cargo:rustc-cfg=netlink_backend"),
+        "linux" | "freebsd" | "openbsd" | "netbsd" | "dragonfly" | "macos" | "android" | "ios"
+        | "aix" | "illumos" | "solaris" => println!("cargo:rustc-cfg=getifaddrs_backend"),
+        _ => {}
+    }
+}
@@ -1,7 +1,4 @@
-use local_ip_address::{list_afinet_netifas, local_ip, local_ipv6};
-// this is only supported on linux currently
-#[cfg(target_os = "linux")]
-use local_ip_address::local_broadcast_ip;
+use local_ip_address::{list_afinet_netifas, local_broadcast_ip, local_ip, local_ipv6};
 
 fn main() {
     match local_ip() {
@@ -14,8 +11,6 @@ fn main() {
         Err(err) => println!("Failed to get local IPv6: {}", err),
     };
 
-    // this is only supported on linux currently
-    #[cfg(target_os = "linux")]
     match local_broadcast_ip() {
         Ok(ip) => println!("Local broadcast IPv4: {}", ip),
         Err(err) => println!("Failed to get local broadcast IPv4: {}", err),
@@ -0,0 +1,169 @@
+//! Opt-in caching of the enumeration of the system's network interfaces.
+
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, Instant};
+
+use crate::{Error, NetworkInterface};
+
+/// Cache of the system's network interfaces as returned by
+/// `list_network_interfaces`, meant for hot paths where enumerating the
+/// network interfaces on every call is too expensive.
+///
+/// The network interfaces are enumerated on the first call to
+/// [`CachedInterfaces::get`] and kept until [`CachedInterfaces::refresh`] is
+/// called or, when built with [`CachedInterfaces::with_ttl`], until the TTL
+/// elapses. The cache can be shared among threads, concurrent reads don't
+/// block each other.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use local_ip_address::CachedInterfaces;
+///
+/// let cache = CachedInterfaces::with_ttl(Duration::from_secs(30));
+///
+/// if let Ok(netifas) = cache.get() {
+///     for netifa in netifas.iter() {
+///         println!("{}:\t{:?}", netifa.name, netifa.addr);
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct CachedInterfaces {
+    ttl: Option<Duration>,
+    enumerate: fn() -> Result<Vec<NetworkInterface>, Error>,
+    entry: RwLock<Option<(Instant, Vec<NetworkInterface>)>>,
+}
+
+impl CachedInterfaces {
+    /// Creates a cache whose network interfaces are kept until
+    /// [`CachedInterfaces::refresh`] is called
+    pub fn new() -> CachedInterfaces {
+        CachedInterfaces::with_enumerate(None, crate::list_network_interfaces)
+    }
+
+    /// Creates a cache whose network interfaces are enumerated again once `ttl`
+    /// has elapsed since they were last enumerated
+    pub fn with_ttl(ttl: Duration) -> CachedInterfaces {
+        CachedInterfaces::with_enumerate(Some(ttl), crate::list_network_interfaces)
+    }
+
+    fn with_enumerate(
+        ttl: Option<Duration>,
+        enumerate: fn() -> Result<Vec<NetworkInterface>, Error>,
+    ) -> CachedInterfaces {
+        CachedInterfaces {
+            ttl,
+            enumerate,
+            entry: RwLock::new(None),
+        }
+    }
+
+    /// Retrieves the cached network interfaces, enumerating them if the cache
+    /// is empty or has expired
+    pub fn get(&self) -> Result<Vec<NetworkInterface>, Error> {
+        if let Some((captured_at, netifas)) = self.read().as_ref() {
+            if !self.is_expired(*captured_at) {
+                return Ok(netifas.clone());
+            }
+        }
+
+        self.refresh()
+    }
+
+    /// Enumerates the network interfaces replacing the cached ones, the cache
+    /// is left untouched if the enumeration fails
+    pub fn refresh(&self) -> Result<Vec<NetworkInterface>, Error> {
+        let netifas = (self.enumerate)()?;
+
+        *self.write() = Some((Instant::now(), netifas.clone()));
+
+        Ok(netifas)
+    }
+
+    fn is_expired(&self, captured_at: Instant) -> bool {
+        match self.ttl {
+            Some(ttl) => captured_at.elapsed() >= ttl,
+            None => false,
+        }
+    }
+
+    // The cached network interfaces are replaced as a whole, thus they are
+    // consistent even if a thread panicked while holding the lock
+    fn read(&self) -> RwLockReadGuard<'_, Option<(Instant, Vec<NetworkInterface>)>> {
+        self.entry.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Option<(Instant, Vec<NetworkInterface>)>> {
+        self.entry.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for CachedInterfaces {
+    fn default() -> CachedInterfaces {
+        CachedInterfaces::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::CachedInterfaces;
+    use crate::{Error, InterfaceFlags, InterfaceKind, NetworkInterface};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counting_enumerate() -> Result<Vec<NetworkInterface>, Error> {
+        let calls = CALLS.fetch_add(1, Ordering::SeqCst) + 1;
+
+        Ok(vec![NetworkInterface {
+            name: format!("eth{}", calls),
+            addr: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10)),
+            prefix_len: Some(24),
+            is_loopback: false,
+            index: Some(2),
+            mac: None,
+            mtu: Some(1500),
+            flags: InterfaceFlags::UP | InterfaceFlags::RUNNING,
+            kind: InterfaceKind::Ethernet,
+            scope_id: None,
+            dest_addr: None,
+            valid_lifetime: None,
+            preferred_lifetime: None,
+        }])
+    }
+
+    fn failing_enumerate() -> Result<Vec<NetworkInterface>, Error> {
+        Err(Error::StrategyError(String::from("enumeration failed")))
+    }
+
+    #[test]
+    fn cache_until_refreshed_or_expired() {
+        let cache = CachedInterfaces::with_enumerate(None, counting_enumerate);
+        let first = cache.get().unwrap();
+
+        assert_eq!(cache.get().unwrap(), first);
+
+        let refreshed = cache.refresh().unwrap();
+
+        assert_ne!(refreshed, first);
+        assert_eq!(cache.get().unwrap(), refreshed);
+
+        let cache = CachedInterfaces::with_enumerate(Some(Duration::ZERO), counting_enumerate);
+        let first = cache.get().unwrap();
+
+        assert_ne!(cache.get().unwrap(), first);
+    }
+
+    #[test]
+    fn failed_enumeration_is_not_cached() {
+        let cache = CachedInterfaces::with_enumerate(None, failing_enumerate);
+
+        assert!(cache.get().is_err());
+        assert!(cache.read().is_none());
+    }
+}
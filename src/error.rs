@@ -1,4 +1,4 @@
-#[derive(thiserror::Error, Debug, PartialEq)]
+#[derive(thiserror::Error, Debug)]
 pub enum Error {
     /// Returned when `local_ip` is unable to find the system's local IP address
     /// in the collection of network interfaces
@@ -8,7 +8,124 @@ pub enum Error {
     /// The error message may include any internal strategy error if available
     #[error("An error occurred executing the underlying strategy error.\n{0}")]
     StrategyError(String),
+    /// Returned when an I/O operation of the strategy fails, such as opening a
+    /// Netlink socket or retrieving the list of interface addresses
+    #[error("An I/O error occurred executing the underlying strategy.\n{0}")]
+    Io(#[from] std::io::Error),
+    /// Returned when `getifaddrs` fails on Unix-based systems, holding its
+    /// return value and the `errno` it set (e.g. `EMFILE` or `ENOMEM`)
+    #[error("An error occurred calling getifaddrs, it returned {ret} with errno {errno}")]
+    GetIfAddrs {
+        /// Value returned by `getifaddrs`
+        ret: i32,
+        /// Value of `errno` set by `getifaddrs`
+        errno: i32,
+    },
+    /// Returned when a Win32 API call fails on Windows, holding the error code
+    /// it returned (e.g. `ERROR_NOT_ENOUGH_MEMORY`) and its localized message
+    #[error("A Win32 API call failed with error code {code}.\n{message}")]
+    Win32 {
+        /// Error code returned by the Win32 API call
+        code: u32,
+        /// Message the system provides for the error code
+        message: String,
+    },
+    /// Returned when no network interface with the given name is available
+    #[error("The network interface `{0}` wasn't found")]
+    InterfaceNotFound(String),
     /// Returned when the current platform is not yet supported
     #[error("The current platform: `{0}`, is not supported")]
     PlatformNotSupported(String),
 }
+
+impl Error {
+    /// Returns `true` if the requested address or network interface wasn't
+    /// found, either `Error::LocalIpAddressNotFound` or `Error::InterfaceNotFound`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use local_ip_address::{local_ip, Error};
+    ///
+    /// match local_ip() {
+    ///     Ok(ip) => println!("This is my local IP address: {:?}", ip),
+    ///     Err(err) if err.is_not_found() => println!("Not connected to a network"),
+    ///     Err(err) => println!("Error getting local IP: {}", err),
+    /// }
+    ///
+    /// assert!(Error::LocalIpAddressNotFound.is_not_found());
+    /// ```
+    pub fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            Error::LocalIpAddressNotFound | Error::InterfaceNotFound(_)
+        )
+    }
+
+    /// Returns `true` if the current platform is not supported, this is
+    /// `Error::PlatformNotSupported`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use local_ip_address::Error;
+    ///
+    /// let err = Error::PlatformNotSupported(String::from("wasi"));
+    ///
+    /// assert!(err.is_unsupported());
+    /// ```
+    pub fn is_unsupported(&self) -> bool {
+        matches!(self, Error::PlatformNotSupported(_))
+    }
+
+    /// Returns `true` if the underlying strategy failed, either
+    /// `Error::StrategyError`, `Error::Io`, `Error::GetIfAddrs` or `Error::Win32`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use local_ip_address::Error;
+    ///
+    /// let err = Error::StrategyError(String::from("Netlink socket failed"));
+    ///
+    /// assert!(err.is_strategy());
+    /// assert!(!err.is_not_found());
+    /// ```
+    pub fn is_strategy(&self) -> bool {
+        matches!(
+            self,
+            Error::StrategyError(_) | Error::Io(_) | Error::GetIfAddrs { .. } | Error::Win32 { .. }
+        )
+    }
+}
+
+/// `std::io::Error` can't be compared, I/O errors are considered equal when
+/// they have the same kind and OS error code
+impl PartialEq for Error {
+    fn eq(&self, other: &Error) -> bool {
+        match (self, other) {
+            (Error::LocalIpAddressNotFound, Error::LocalIpAddressNotFound) => true,
+            (Error::StrategyError(a), Error::StrategyError(b)) => a == b,
+            (Error::Io(a), Error::Io(b)) => {
+                a.kind() == b.kind() && a.raw_os_error() == b.raw_os_error()
+            }
+            (
+                Error::GetIfAddrs { ret, errno },
+                Error::GetIfAddrs {
+                    ret: other_ret,
+                    errno: other_errno,
+                },
+            ) => ret == other_ret && errno == other_errno,
+            (
+                Error::Win32 { code, message },
+                Error::Win32 {
+                    code: other_code,
+                    message: other_message,
+                },
+            ) => code == other_code && message == other_message,
+            (Error::InterfaceNotFound(a), Error::InterfaceNotFound(b)) => a == b,
+            (Error::PlatformNotSupported(a), Error::PlatformNotSupported(b)) => a == b,
+            _ => false,
+        }
+    }
+}
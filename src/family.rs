@@ -0,0 +1,26 @@
+//! Address families used to narrow down the search of network interfaces.
+
+/// Socket address family of the addresses to retrieve, each variant maps to
+/// the `AF_INET`, `AF_INET6` and `AF_UNSPEC` constant of the running platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddrFamily {
+    /// IPv4 addresses (`AF_INET`)
+    Inet,
+    /// IPv6 addresses (`AF_INET6`)
+    Inet6,
+    /// Addresses of both families (`AF_UNSPEC`)
+    Unspec,
+}
+
+impl AddrFamily {
+    /// Determines if the address belongs to the family
+    #[cfg(getifaddrs_backend)]
+    pub(crate) fn contains(&self, addr: &std::net::IpAddr) -> bool {
+        matches!(
+            (self, addr),
+            (AddrFamily::Unspec, _)
+                | (AddrFamily::Inet, std::net::IpAddr::V4(_))
+                | (AddrFamily::Inet6, std::net::IpAddr::V6(_))
+        )
+    }
+}
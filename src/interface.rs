@@ -0,0 +1,473 @@
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::ops::{BitOr, BitOrAssign};
+
+/// Network interface address belonging to the `AF_INET` or `AF_INET6` family
+/// along with details on the network interface holding it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceAddr {
+    /// Name of the network interface, on Windows this is the adapter's
+    /// friendly name
+    pub name: String,
+    /// Address assigned to the network interface
+    pub addr: IpAddr,
+    /// Whether the network interface is a loopback interface
+    pub is_loopback: bool,
+    /// Whether the address is the primary address of the network interface,
+    /// refer to `list_afinet_netifas_with_primary` for details on each platform
+    pub is_primary: bool,
+    /// Whether the network interface is up and running, this is both `IFF_UP`
+    /// and `IFF_RUNNING` are set on Linux and Unix-based systems, and the
+    /// adapter's `OperStatus` is `IfOperStatusUp` on Windows
+    pub is_up: bool,
+    /// Whether the network interface supports multicast
+    pub is_multicast: bool,
+    /// Length of the network prefix of the address, the address is considered
+    /// a host address (`/32` or `/128`) if the platform doesn't report it
+    pub prefix_len: u8,
+    /// Medium of the network interface
+    pub kind: InterfaceKind,
+}
+
+/// Network interface address belonging to the `AF_INET` or `AF_INET6` family
+/// along with the metadata each platform reports for the network interface
+/// holding it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkInterface {
+    /// Name of the network interface, on Windows this is the adapter's
+    /// friendly name
+    pub name: String,
+    /// Address assigned to the network interface
+    pub addr: IpAddr,
+    /// Length of the network prefix of the address, `None` if the platform
+    /// doesn't report it
+    pub prefix_len: Option<u8>,
+    /// Whether the network interface is a loopback interface
+    pub is_loopback: bool,
+    /// Index of the network interface, on Windows this is the IPv4 or IPv6
+    /// interface index of the adapter depending on the family of the address
+    pub index: Option<u32>,
+    /// MAC (hardware) address of the network interface, `None` for interfaces
+    /// without an EUI-48 hardware address such as loopback or tunnel interfaces.
+    /// With the `macaddr` feature it's also available as a `macaddr::MacAddr6`
+    /// through `NetworkInterface::mac_addr`
+    pub mac: Option<[u8; 6]>,
+    /// Maximum transmission unit of the network interface in bytes, `None` for
+    /// loopback interfaces and for interfaces that don't report it
+    pub mtu: Option<u32>,
+    /// Operational flags of the network interface
+    pub flags: InterfaceFlags,
+    /// Medium of the network interface, mapped from the `ARPHRD_*` link type on
+    /// Linux and from the adapter's `IfType` on Windows
+    pub kind: InterfaceKind,
+    /// Scope id (zone index) of the address, only reported for link-local IPv6
+    /// addresses (`fe80::/10`) which can't be reached without it
+    pub scope_id: Option<u32>,
+    /// Broadcast address of the address on interfaces supporting broadcast, or
+    /// address of the peer on point-to-point interfaces
+    pub dest_addr: Option<DestAddr>,
+    /// Remaining time in seconds the address is valid for, `u32::MAX` when it
+    /// never expires. Only reported on Linux (`IFA_CACHEINFO`) and Windows
+    pub valid_lifetime: Option<u32>,
+    /// Remaining time in seconds the address is preferred for, `u32::MAX` when
+    /// it never expires. A deprecated address, such as an expired temporary
+    /// IPv6 address, has a preferred lifetime of zero. Only reported on Linux
+    /// (`IFA_CACHEINFO`) and Windows
+    pub preferred_lifetime: Option<u32>,
+}
+
+/// Destination address associated with an address of a network interface, as
+/// reported in the `ifa_broadaddr`/`ifa_dstaddr` union on Unix-based systems
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DestAddr {
+    /// Broadcast address of the network the address belongs to, on Windows it's
+    /// computed from the address and the length of its on-link prefix
+    Broadcast(IpAddr),
+    /// Address of the peer on the other end of a point-to-point link, not
+    /// reported on Windows
+    PointToPoint(IpAddr),
+}
+
+/// Medium of a network interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InterfaceKind {
+    /// Wired Ethernet interface, on Linux only interfaces backed by a device
+    Ethernet,
+    /// Wireless (IEEE 802.11) interface
+    Wireless,
+    /// Loopback interface
+    Loopback,
+    /// Tunnel interface, as used by VPNs
+    Tunnel,
+    /// Interface of a known medium not covered by other variants, including
+    /// virtual Ethernet interfaces such as bridges on Linux
+    Other,
+    /// The medium of the interface can't be determined, as is the case for
+    /// non-loopback interfaces on BSD-based systems
+    Unknown,
+}
+
+/// Set of operational flags of a network interface, as reported in `ifa_flags`
+/// on Unix-based systems
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::InterfaceFlags;
+///
+/// let flags = InterfaceFlags::UP | InterfaceFlags::RUNNING;
+///
+/// assert!(flags.contains(InterfaceFlags::UP));
+/// assert!(!flags.contains(InterfaceFlags::UP | InterfaceFlags::MULTICAST));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct InterfaceFlags(u32);
+
+impl InterfaceFlags {
+    /// The interface is administratively up (`IFF_UP`)
+    pub const UP: InterfaceFlags = InterfaceFlags(1);
+    /// The interface is operational, its resources are allocated (`IFF_RUNNING`)
+    pub const RUNNING: InterfaceFlags = InterfaceFlags(1 << 1);
+    /// The interface supports broadcast (`IFF_BROADCAST`)
+    pub const BROADCAST: InterfaceFlags = InterfaceFlags(1 << 2);
+    /// The interface supports multicast (`IFF_MULTICAST`)
+    pub const MULTICAST: InterfaceFlags = InterfaceFlags(1 << 3);
+    /// The interface is a point-to-point link (`IFF_POINTOPOINT`)
+    pub const POINTOPOINT: InterfaceFlags = InterfaceFlags(1 << 4);
+
+    /// Creates a set without any flag
+    pub const fn empty() -> InterfaceFlags {
+        InterfaceFlags(0)
+    }
+
+    /// Retrieves the raw value of the set, flags are not encoded as their
+    /// platform-specific values
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Determines if the set holds no flag
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Determines if every flag of `other` is held by the set
+    pub const fn contains(&self, other: InterfaceFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Adds the flags of `other` to the set
+    pub fn insert(&mut self, other: InterfaceFlags) {
+        self.0 |= other.0;
+    }
+
+    /// Adds the flags of `other` to the set when `value` is `true`
+    #[allow(dead_code)]
+    pub(crate) fn set(&mut self, other: InterfaceFlags, value: bool) {
+        if value {
+            self.insert(other);
+        }
+    }
+}
+
+impl BitOr for InterfaceFlags {
+    type Output = InterfaceFlags;
+
+    fn bitor(self, rhs: InterfaceFlags) -> InterfaceFlags {
+        InterfaceFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for InterfaceFlags {
+    fn bitor_assign(&mut self, rhs: InterfaceFlags) {
+        self.insert(rhs);
+    }
+}
+
+impl InterfaceAddr {
+    /// Retrieves the network address of the interface address, this is the
+    /// address masked with its network prefix
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::net::{IpAddr, Ipv4Addr};
+    /// use local_ip_address::{InterfaceAddr, InterfaceKind};
+    ///
+    /// let ifa = InterfaceAddr {
+    ///     name: String::from("eth0"),
+    ///     addr: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10)),
+    ///     is_loopback: false,
+    ///     is_primary: true,
+    ///     is_up: true,
+    ///     is_multicast: true,
+    ///     prefix_len: 24,
+    ///     kind: InterfaceKind::Ethernet,
+    /// };
+    ///
+    /// assert_eq!(ifa.network(), IpAddr::V4(Ipv4Addr::new(192, 168, 1, 0)));
+    /// ```
+    pub fn network(&self) -> IpAddr {
+        match (self.addr, self.netmask()) {
+            (IpAddr::V4(addr), IpAddr::V4(netmask)) => {
+                IpAddr::V4(Ipv4Addr::from(u32::from(addr) & u32::from(netmask)))
+            }
+            (IpAddr::V6(addr), IpAddr::V6(netmask)) => {
+                IpAddr::V6(Ipv6Addr::from(u128::from(addr) & u128::from(netmask)))
+            }
+            _ => unreachable!("the netmask belongs to the address family"),
+        }
+    }
+
+    /// Retrieves the netmask of the interface address built from its network
+    /// prefix, as an address of the same family
+    pub fn netmask(&self) -> IpAddr {
+        match self.addr {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(
+                u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix_len.min(32)))
+                    .unwrap_or(0),
+            )),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(
+                u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix_len.min(128)))
+                    .unwrap_or(0),
+            )),
+        }
+    }
+
+    /// Determines if an interface is used for mobile_data
+    #[allow(dead_code)]
+    pub(crate) fn is_mobile_data(&self) -> bool {
+        self.name.contains("rmnet_data")
+    }
+}
+
+/// Formats the interface address as `name: addr`, followed by ` (loopback)` for
+/// loopback interfaces
+///
+/// # Example
+///
+/// ```
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use local_ip_address::{InterfaceAddr, InterfaceKind};
+///
+/// let ifa = InterfaceAddr {
+///     name: String::from("lo"),
+///     addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+///     is_loopback: true,
+///     is_primary: true,
+///     is_up: true,
+///     is_multicast: false,
+///     prefix_len: 8,
+///     kind: InterfaceKind::Loopback,
+/// };
+///
+/// assert_eq!(ifa.to_string(), "lo: 127.0.0.1 (loopback)");
+/// ```
+impl fmt::Display for InterfaceAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.addr)?;
+
+        if self.is_loopback {
+            write!(f, " (loopback)")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl NetworkInterface {
+    /// Builds the socket address of the interface address with the given
+    /// `port`, carrying the scope id of link-local IPv6 addresses so the
+    /// resulting `SocketAddrV6` is reachable through this interface.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::net::TcpListener;
+    /// use local_ip_address::list_network_interfaces;
+    ///
+    /// if let Ok(netifas) = list_network_interfaces() {
+    ///     if let Some(netifa) = netifas.iter().find(|netifa| netifa.is_loopback) {
+    ///         let listener = TcpListener::bind(netifa.socket_addr(0)).unwrap();
+    ///
+    ///         println!("Listening on {}", listener.local_addr().unwrap());
+    ///     }
+    /// }
+    /// ```
+    pub fn socket_addr(&self, port: u16) -> SocketAddr {
+        match self.addr {
+            IpAddr::V4(addr) => SocketAddr::V4(SocketAddrV4::new(addr, port)),
+            IpAddr::V6(addr) => {
+                SocketAddr::V6(SocketAddrV6::new(addr, port, 0, self.scope_id.unwrap_or(0)))
+            }
+        }
+    }
+
+    /// MAC (hardware) address of the network interface as a
+    /// `macaddr::MacAddr6`, which can be displayed and parsed. The raw bytes
+    /// are available in [`NetworkInterface::mac`] without the `macaddr`
+    /// feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use local_ip_address::list_network_interfaces;
+    ///
+    /// for netifa in list_network_interfaces().unwrap() {
+    ///     if let Some(mac) = netifa.mac_addr() {
+    ///         println!("{}:\t{}", netifa.name, mac);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "macaddr")]
+    pub fn mac_addr(&self) -> Option<macaddr::MacAddr6> {
+        self.mac.map(macaddr::MacAddr6::from)
+    }
+}
+
+/// Builds a MAC address from the hardware address reported by the platform,
+/// only EUI-48 addresses are kept and the all-zeros address some interfaces
+/// report (e.g. loopback on Linux) is discarded
+#[allow(dead_code)]
+pub(crate) fn mac_from_bytes(bytes: &[u8]) -> Option<[u8; 6]> {
+    let mut mac = [0; 6];
+
+    if bytes.len() != mac.len() || bytes.iter().all(|byte| *byte == 0) {
+        return None;
+    }
+
+    mac.copy_from_slice(bytes);
+    Some(mac)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::{mac_from_bytes, InterfaceAddr, InterfaceFlags, InterfaceKind, NetworkInterface};
+
+    fn ifa(addr: &str, prefix_len: u8) -> InterfaceAddr {
+        InterfaceAddr {
+            name: String::from("eth0"),
+            addr: addr.parse().unwrap(),
+            is_loopback: false,
+            is_primary: true,
+            is_up: true,
+            is_multicast: true,
+            prefix_len,
+            kind: InterfaceKind::Ethernet,
+        }
+    }
+
+    fn ip(addr: &str) -> IpAddr {
+        addr.parse().unwrap()
+    }
+
+    #[test]
+    fn network_masks_ipv4_addresses() {
+        assert_eq!(ifa("192.168.1.10", 24).network(), ip("192.168.1.0"));
+        assert_eq!(ifa("10.20.30.40", 12).network(), ip("10.16.0.0"));
+        assert_eq!(ifa("10.20.30.40", 32).network(), ip("10.20.30.40"));
+        assert_eq!(ifa("10.20.30.40", 0).network(), ip("0.0.0.0"));
+    }
+
+    #[test]
+    fn network_masks_ipv6_addresses() {
+        assert_eq!(ifa("2001:db8:1:2::10", 64).network(), ip("2001:db8:1:2::"));
+        assert_eq!(ifa("2001:db8:1:2::10", 36).network(), ip("2001:db8::"));
+        assert_eq!(ifa("2001:db8::1", 128).network(), ip("2001:db8::1"));
+        assert_eq!(ifa("2001:db8::1", 0).network(), ip("::"));
+    }
+
+    #[test]
+    fn netmask_from_prefix_len() {
+        assert_eq!(ifa("192.168.1.10", 24).netmask(), ip("255.255.255.0"));
+        assert_eq!(ifa("192.168.1.10", 0).netmask(), ip("0.0.0.0"));
+        assert_eq!(ifa("fe80::1", 64).netmask(), ip("ffff:ffff:ffff:ffff::"));
+    }
+
+    #[test]
+    fn display_interface_address() {
+        assert_eq!(ifa("192.168.1.10", 24).to_string(), "eth0: 192.168.1.10");
+        assert_eq!(ifa("fe80::1", 64).to_string(), "eth0: fe80::1");
+    }
+
+    #[test]
+    fn mac_from_hardware_address() {
+        assert_eq!(
+            mac_from_bytes(&[0x02, 0x42, 0xac, 0x11, 0x00, 0x02]),
+            Some([0x02, 0x42, 0xac, 0x11, 0x00, 0x02])
+        );
+        assert_eq!(mac_from_bytes(&[0; 6]), None);
+        assert_eq!(mac_from_bytes(&[]), None);
+        assert_eq!(mac_from_bytes(&[0x02, 0x42, 0xac, 0x11]), None);
+    }
+
+    #[test]
+    fn flags_set_operations() {
+        let mut flags = InterfaceFlags::empty();
+        assert!(flags.is_empty());
+
+        flags.set(InterfaceFlags::UP, true);
+        flags.set(InterfaceFlags::RUNNING, false);
+        flags |= InterfaceFlags::MULTICAST;
+
+        assert_eq!(flags, InterfaceFlags::UP | InterfaceFlags::MULTICAST);
+        assert!(flags.contains(InterfaceFlags::UP));
+        assert!(!flags.contains(InterfaceFlags::UP | InterfaceFlags::RUNNING));
+        assert!(flags.contains(InterfaceFlags::empty()));
+    }
+
+    fn netifa(addr: &str) -> NetworkInterface {
+        NetworkInterface {
+            name: String::from("eth0"),
+            addr: ip(addr),
+            prefix_len: Some(24),
+            is_loopback: false,
+            index: Some(2),
+            mac: None,
+            mtu: Some(1500),
+            flags: InterfaceFlags::UP | InterfaceFlags::RUNNING,
+            kind: InterfaceKind::Ethernet,
+            scope_id: None,
+            dest_addr: None,
+            valid_lifetime: None,
+            preferred_lifetime: None,
+        }
+    }
+
+    #[test]
+    fn socket_addr_carries_scope_id() {
+        let mut netifa = netifa("192.168.1.10");
+
+        assert_eq!(
+            netifa.socket_addr(8080),
+            "192.168.1.10:8080".parse().unwrap()
+        );
+
+        netifa.addr = ip("fe80::1");
+        netifa.scope_id = Some(2);
+
+        assert_eq!(
+            netifa.socket_addr(8080),
+            "[fe80::1%2]:8080".parse().unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "macaddr")]
+    fn mac_addr_from_mac() {
+        let mut netifa = netifa("192.168.1.10");
+
+        assert_eq!(netifa.mac_addr(), None);
+
+        netifa.mac = Some([0x02, 0x42, 0xac, 0x11, 0x00, 0x02]);
+
+        assert_eq!(
+            netifa.mac_addr().map(|mac| mac.to_string()),
+            Some(String::from("02:42:AC:11:00:02"))
+        );
+    }
+}
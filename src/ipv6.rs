@@ -0,0 +1,60 @@
+//! Helpers to decompose IPv6 addresses.
+
+use std::net::Ipv6Addr;
+
+/// Splits an IPv6 address into its network prefix (upper 64 bits) and its
+/// interface identifier (lower 64 bits), as used by SLAAC (RFC 4862) and
+/// DHCPv6 for `/64` networks.
+///
+/// # Example
+///
+/// ```
+/// use std::net::Ipv6Addr;
+/// use local_ip_address::split_ipv6;
+///
+/// let addr: Ipv6Addr = "2001:db8:1:2::10".parse().unwrap();
+///
+/// assert_eq!(split_ipv6(&addr), (0x2001_0db8_0001_0002, 0x10));
+/// ```
+pub fn split_ipv6(addr: &Ipv6Addr) -> (u64, u64) {
+    let bits = u128::from(*addr);
+
+    ((bits >> 64) as u64, bits as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv6Addr;
+
+    use super::split_ipv6;
+
+    fn v6(addr: &str) -> Ipv6Addr {
+        addr.parse().unwrap()
+    }
+
+    #[test]
+    fn split_global_address() {
+        assert_eq!(
+            split_ipv6(&v6("2001:db8:1:2:a:b:c:d")),
+            (0x2001_0db8_0001_0002, 0x000a_000b_000c_000d)
+        );
+    }
+
+    #[test]
+    fn split_eui64_interface_identifier() {
+        assert_eq!(
+            split_ipv6(&v6("fe80::211:22ff:fe33:4455")),
+            (0xfe80_0000_0000_0000, 0x0211_22ff_fe33_4455)
+        );
+    }
+
+    #[test]
+    fn split_boundaries() {
+        assert_eq!(split_ipv6(&Ipv6Addr::UNSPECIFIED), (0, 0));
+        assert_eq!(split_ipv6(&Ipv6Addr::LOCALHOST), (0, 1));
+        assert_eq!(
+            split_ipv6(&v6("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff")),
+            (u64::MAX, u64::MAX)
+        );
+    }
+}
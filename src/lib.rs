@@ -42,8 +42,8 @@ may differ based on the running operative system.
 
 OS | Approach
 --- | ---
-Linux | Establishes a Netlink socket interchange to retrieve network interfaces
-BSD-based & Android | Uses of `getifaddrs` to retrieve network interfaces
+Linux | Establishes a Netlink socket interchange to retrieve network interfaces, or uses `getifaddrs` with the `linux-getifaddrs` feature or without the `netlink` feature
+BSD-based, Android, AIX & illumos | Uses of `getifaddrs` to retrieve network interfaces
 Windows | Consumes Win32 API's to retrieve the network adapters table
 
 Supported BSD-based systems include:
@@ -52,39 +52,74 @@ Supported BSD-based systems include:
   - OpenBSD
   - NetBSD
   - DragonFly
+
+Other Unix systems providing `getifaddrs` are supported as well:
+  - AIX
+  - illumos
+  - Solaris
+
+## Thread Safety
+
+The data types of this crate (`InterfaceAddr`, `NetworkInterface`,
+`InterfaceKind`, `InterfaceFlags`, `DestAddr`, `AddrFamily`, `AddrScope`,
+`Snapshot`, `Change` and `WindowsOptions`) are `Send + Sync` and cheap to
+clone, no OS handle is held by them. `Error` is `Send + Sync` as well but is
+not `Clone`, as `Error::Io` wraps a `std::io::Error`.
+
+OS resources such as Netlink sockets or the `getifaddrs` list are acquired and
+released within each function call, these functions are safe to call from
+multiple threads concurrently. The exception is `NetlinkClient` on Linux, which
+owns its Netlink socket across calls and requires exclusive access to query
+it. `CachedInterfaces` is `Send + Sync` as well and is meant to be shared among
+threads.
+
+## Panics
+
+Functions of this crate never panic on data returned by the OS, malformed or
+unexpected data is reported as an `Error` instead.
 */
 
-use std::net::IpAddr;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddrV6, TcpListener, UdpSocket};
+use std::time::{Duration, Instant};
 
+mod cache;
 mod error;
+mod family;
+mod interface;
+mod ipv6;
+#[cfg(feature = "tokio")]
+mod nonblocking;
+#[cfg(any(netlink_backend, getifaddrs_backend))]
+mod resolv;
+mod scope;
+mod selection;
+mod snapshot;
 
+pub use cache::CachedInterfaces;
 pub use error::Error;
+pub use family::AddrFamily;
+pub use interface::{DestAddr, InterfaceAddr, InterfaceFlags, InterfaceKind, NetworkInterface};
+pub use ipv6::split_ipv6;
+#[cfg(feature = "tokio")]
+pub use nonblocking::{list_afinet_netifas_async, local_ip_async};
+pub use scope::{addr_scope, AddrScope};
+pub use selection::source_candidates;
+pub use snapshot::{Change, Snapshot};
 
-#[cfg(target_os = "linux")]
+// The backend is selected by build.rs, neli is only pulled by the `netlink`
+// feature, enabled by default, the getifaddrs backend is used on Linux when
+// it's disabled or when the `linux-getifaddrs` feature is enabled
+#[cfg(netlink_backend)]
 pub mod linux;
-#[cfg(target_os = "linux")]
+#[cfg(netlink_backend)]
 pub use crate::linux::*;
 
-#[cfg(any(
-    target_os = "freebsd",
-    target_os = "openbsd",
-    target_os = "netbsd",
-    target_os = "dragonfly",
-    target_os = "macos",
-    target_os = "android",
-    target_os = "ios",
-))]
+#[cfg(getifaddrs_backend)]
 pub mod unix;
 
-#[cfg(any(
-    target_os = "freebsd",
-    target_os = "openbsd",
-    target_os = "netbsd",
-    target_os = "dragonfly",
-    target_os = "macos",
-    target_os = "android",
-    target_os = "ios",
-))]
+#[cfg(getifaddrs_backend)]
 pub use crate::unix::*;
 
 #[cfg(target_family = "windows")]
@@ -98,38 +133,91 @@ pub use crate::windows::*;
 /// A different approach is taken based on the operative system.
 ///
 /// For linux based systems the Netlink socket communication is used to
-/// retrieve the local network interface.
+/// retrieve the local network interface, unless the `linux-getifaddrs`
+/// feature is enabled or the `netlink` feature is disabled, in which case the
+/// BSD-based approach is taken.
 ///
 /// For BSD-based systems the `getifaddrs` approach is taken using `libc`
 ///
 /// For Windows systems Win32's IP Helper is used to gather the Local IP
 /// address
+///
+/// When several interfaces hold a default route (e.g. wifi and a container
+/// bridge), the address of the one the routing table prefers is returned: the
+/// default route adapter with the lowest metric on Windows, and the interface
+/// holding the address [`local_ip_to`] selects towards an external address on
+/// BSD-based systems. The first primary address of an interface which is up is
+/// returned on BSD-based systems when there is no default route.
+///
+/// The primary address of the chosen interface is preferred over its secondary
+/// (alias) addresses: addresses flagged with `IFA_F_SECONDARY` are skipped on
+/// Linux, and the first address of the interface listed by `getifaddrs` is
+/// taken as the primary one on BSD-based systems as aliases are listed after it.
+///
+/// A loopback address is never returned, on any platform.
 pub fn local_ip() -> Result<IpAddr, Error> {
-    #[cfg(target_os = "linux")]
+    #[cfg(netlink_backend)]
     {
         crate::linux::local_ip()
     }
 
-    #[cfg(any(
-        target_os = "freebsd",
-        target_os = "openbsd",
-        target_os = "netbsd",
-        target_os = "dragonfly",
-        target_os = "macos",
-        target_os = "android",
-        target_os = "ios",
-    ))]
+    #[cfg(getifaddrs_backend)]
     {
-        let ifas = crate::unix::list_afinet_netifas_info()?;
-
-        ifas.into_iter()
-            .find_map(|ifa| {
-                if !ifa.is_loopback && ifa.addr.is_ipv4() && !ifa.is_mobile_data() {
-                    Some(ifa.addr)
-                } else {
-                    None
-                }
-            })
+        local_ip_detailed().map(|(_, ip_address)| ip_address)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        local_ip_detailed().map(|(_, ip_address)| ip_address)
+    }
+
+    // A catch-all case to error if not implemented for OS
+    #[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+    {
+        Err(Error::PlatformNotSupported(
+            std::env::consts::OS.to_string(),
+        ))
+    }
+}
+
+/// Retrieves the local IPv4 address of the machine just as [`local_ip`] does,
+/// along with the name of the network interface holding it, which can be used
+/// to look up further details of such interface (e.g. its MAC address or MTU
+/// through [`list_network_interfaces`]).
+///
+/// When several interfaces hold the local IP address, the one on the default
+/// route is returned. On Linux the name is the one of the output interface of
+/// the route used to find the local IP address. On Windows it's the friendly
+/// name of the default route adapter with the lowest metric holding the
+/// address, and on BSD-based systems the name of the interface holding the
+/// address [`local_ip_to`] selects towards an external address.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::local_ip_detailed;
+///
+/// if let Ok((name, ip)) = local_ip_detailed() {
+///     println!("This is my local IP address: {:?} ({})", ip, name);
+/// }
+/// ```
+pub fn local_ip_detailed() -> Result<(String, IpAddr), Error> {
+    #[cfg(netlink_backend)]
+    {
+        crate::linux::local_ip_detailed()
+    }
+
+    #[cfg(getifaddrs_backend)]
+    {
+        // addresses of other families are skipped while walking the list
+        let ifas = crate::unix::list_afinet_netifas_info_impl(AddrFamily::Inet)?;
+
+        // the source address the routing table selects towards a reserved
+        // external address belongs to the interface with the best default route
+        let routed_ip = local_ip_to(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 0))).ok();
+
+        crate::selection::select_local_ifa(&ifas, routed_ip)
+            .map(|ifa| (ifa.name.clone(), ifa.addr))
             .ok_or(Error::LocalIpAddressNotFound)
     }
 
@@ -141,22 +229,12 @@ pub fn local_ip() -> Result<IpAddr, Error> {
 
         ip_addresses
             .into_iter()
-            .find(|ip_address| matches!(ip_address, IpAddr::V4(_)))
+            .find(|(_, ip_address)| matches!(ip_address, IpAddr::V4(_)))
             .ok_or(Error::LocalIpAddressNotFound)
     }
 
     // A catch-all case to error if not implemented for OS
-    #[cfg(not(any(
-        target_os = "linux",
-        target_os = "windows",
-        target_os = "macos",
-        target_os = "freebsd",
-        target_os = "openbsd",
-        target_os = "netbsd",
-        target_os = "dragonfly",
-        target_os = "android",
-        target_os = "ios",
-    )))]
+    #[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
     {
         Err(Error::PlatformNotSupported(
             std::env::consts::OS.to_string(),
@@ -164,6 +242,189 @@ pub fn local_ip() -> Result<IpAddr, Error> {
     }
 }
 
+/// Retrieves the local IPv4 address of the machine preferring wired interfaces
+/// over wireless ones.
+///
+/// This is a shorthand for [`local_ip_preferring`] with
+/// [`InterfaceKind::Ethernet`]: the address of a wired interface which is up is
+/// returned, otherwise, including when the medium of the interfaces can't be
+/// determined, [`local_ip`] is used.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::local_ip_prefer_wired;
+///
+/// if let Ok(ip) = local_ip_prefer_wired() {
+///     println!("This is my local IP address: {:?}", ip);
+/// }
+/// ```
+pub fn local_ip_prefer_wired() -> Result<IpAddr, Error> {
+    local_ip_preferring(InterfaceKind::Ethernet)
+}
+
+/// Retrieves the local IPv4 address of the machine preferring interfaces of the
+/// given medium.
+///
+/// The primary IPv4 address of the first interface of the given
+/// [`InterfaceKind`] which is up and isn't a loopback interface is returned.
+/// If no such interface exists, including when the medium of the interfaces
+/// can't be determined, [`local_ip`] is used.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::{local_ip_preferring, InterfaceKind};
+///
+/// if let Ok(ip) = local_ip_preferring(InterfaceKind::Ethernet) {
+///     println!("This is my local IP address: {:?}", ip);
+/// }
+/// ```
+pub fn local_ip_preferring(kind: InterfaceKind) -> Result<IpAddr, Error> {
+    let preferred = list_afinet_netifas_info()?.into_iter().find(|ifa| {
+        ifa.addr.is_ipv4() && !ifa.is_loopback && ifa.is_up && ifa.is_primary && ifa.kind == kind
+    });
+
+    match preferred {
+        Some(ifa) => Ok(ifa.addr),
+        None => local_ip(),
+    }
+}
+
+/// Retrieves the local IPv4 address of the machine without enumerating network
+/// interfaces.
+///
+/// An UDP socket is connected to an address reserved for documentation
+/// (`192.0.2.1`, RFC 5737) and the local address the operative system bound
+/// it to is read. No datagram is sent. If the socket approach fails, as when
+/// no route to the reserved address is available, [`local_ip`] is used.
+///
+/// The address returned is the source address of the route towards the
+/// reserved address, which might differ from the one returned by [`local_ip`]:
+/// for instance the address of a VPN tunnel if one holds the default route, or
+/// an address on an interface which [`local_ip`] would skip.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::local_ip_fast;
+///
+/// if let Ok(ip) = local_ip_fast() {
+///     println!("This is my local IP address: {:?}", ip);
+/// }
+/// ```
+pub fn local_ip_fast() -> Result<IpAddr, Error> {
+    let socket_ip = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(192, 0, 2, 1), 9))?;
+            socket.local_addr()
+        })
+        .map(|local_addr| local_addr.ip());
+
+    match socket_ip {
+        Ok(ip) if !ip.is_unspecified() => Ok(ip),
+        _ => local_ip(),
+    }
+}
+
+/// Retrieves the local IP address just as [`local_ip`] does, retrying every
+/// `poll` until it succeeds or `timeout` elapses, which is useful while DHCP
+/// completes during early boot or right after a VPN connects.
+///
+/// The error of the last attempt is returned once `timeout` elapses, and
+/// `Error::PlatformNotSupported` is returned right away as retrying can't help.
+/// A `poll` shorter than 10 milliseconds is raised to it, so interfaces are not
+/// enumerated in a busy loop.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use local_ip_address::wait_for_local_ip;
+///
+/// match wait_for_local_ip(Duration::from_secs(5), Duration::from_millis(250)) {
+///     Ok(ip) => println!("This is my local IP address: {:?}", ip),
+///     Err(err) => println!("No local IP address after 5 seconds: {}", err),
+/// }
+/// ```
+pub fn wait_for_local_ip(timeout: Duration, poll: Duration) -> Result<IpAddr, Error> {
+    retry_until(timeout, poll, local_ip)
+}
+
+/// Shortest interval between two attempts of [`retry_until`]
+const MIN_POLL: Duration = Duration::from_millis(10);
+
+/// Calls `f` every `poll`, raised to [`MIN_POLL`], until it succeeds or
+/// `timeout` elapses, refer to [`wait_for_local_ip`]
+fn retry_until<T>(
+    timeout: Duration,
+    poll: Duration,
+    mut f: impl FnMut() -> Result<T, Error>,
+) -> Result<T, Error> {
+    // a timeout too large to be represented waits indefinitely
+    let deadline = Instant::now().checked_add(timeout);
+    let poll = poll.max(MIN_POLL);
+
+    loop {
+        let err = match f() {
+            Ok(value) => return Ok(value),
+            Err(err) if err.is_unsupported() => return Err(err),
+            Err(err) => err,
+        };
+
+        let remaining = match deadline {
+            Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+            None => poll,
+        };
+
+        if remaining == Duration::ZERO {
+            return Err(err);
+        }
+
+        std::thread::sleep(poll.min(remaining));
+    }
+}
+
+/// Retrieves the local address the operative system would use as source address
+/// when sending packets to `dest`, honoring its routing table.
+///
+/// An UDP socket of the family of `dest` is connected to `dest` and the local
+/// address the operative system bound it to is read. Connecting an UDP socket
+/// only selects a route, no datagram is sent and the call never blocks. The
+/// discard port (`9`) is used as Windows rejects connecting to port `0`.
+///
+/// Returns `Error::LocalIpAddressNotFound` when there is no route to `dest`.
+///
+/// # Example
+///
+/// ```
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use local_ip_address::local_ip_to;
+///
+/// let dest = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+///
+/// if let Ok(ip) = local_ip_to(dest) {
+///     println!("Source address towards {}: {:?}", dest, ip);
+/// }
+/// ```
+pub fn local_ip_to(dest: IpAddr) -> Result<IpAddr, Error> {
+    let bind_addr = match dest {
+        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    let socket = UdpSocket::bind((bind_addr, 0))?;
+
+    socket
+        .connect((dest, 9))
+        .map_err(|_| Error::LocalIpAddressNotFound)?;
+
+    match socket.local_addr() {
+        Ok(local_addr) if !local_addr.ip().is_unspecified() => Ok(local_addr.ip()),
+        Ok(_) => Err(Error::LocalIpAddressNotFound),
+        Err(err) => Err(Error::Io(err)),
+    }
+}
+
 /// Retrieves the local IPv6 address of the machine in the local network from
 /// the `AF_INET6` family.
 ///
@@ -172,37 +433,25 @@ pub fn local_ip() -> Result<IpAddr, Error> {
 /// For linux based systems the Netlink socket communication is used to
 /// retrieve the local network interface.
 ///
-/// For BSD-based systems the `getifaddrs` approach is taken using `libc`
+/// For BSD-based systems the `getifaddrs` approach is taken using `libc`,
+/// addresses are picked as described in [`local_ipv6_opts`]
 ///
 /// For Windows systems Win32's IP Helper is used to gather the Local IP
 /// address
+///
+/// Addresses are preferred by scope in the following order: global addresses,
+/// unique-local addresses (`fc00::/7`), link-local addresses (`fe80::/10`).
+/// On Linux the kernel's source address selection is relied upon, which
+/// follows the same order.
 pub fn local_ipv6() -> Result<IpAddr, Error> {
-    #[cfg(target_os = "linux")]
+    #[cfg(netlink_backend)]
     {
         crate::linux::local_ipv6()
     }
 
-    #[cfg(any(
-        target_os = "freebsd",
-        target_os = "openbsd",
-        target_os = "netbsd",
-        target_os = "dragonfly",
-        target_os = "macos",
-        target_os = "android",
-        target_os = "ios",
-    ))]
+    #[cfg(getifaddrs_backend)]
     {
-        let ifas = crate::unix::list_afinet_netifas_info()?;
-
-        ifas.into_iter()
-            .find_map(|ifa| {
-                if !ifa.is_loopback && ifa.addr.is_ipv6() && !ifa.is_mobile_data() {
-                    Some(ifa.addr)
-                } else {
-                    None
-                }
-            })
-            .ok_or(Error::LocalIpAddressNotFound)
+        local_ipv6_opts(true, false)
     }
 
     #[cfg(target_os = "windows")]
@@ -211,24 +460,18 @@ pub fn local_ipv6() -> Result<IpAddr, Error> {
 
         let ip_addresses = crate::windows::list_local_ip_addresses(AF_INET6)?;
 
+        // the first address is kept among addresses of a same scope, thus the
+        // metric order is kept
         ip_addresses
             .into_iter()
-            .find(|ip_address| matches!(ip_address, IpAddr::V6(_)))
+            .map(|(_, ip_address)| ip_address)
+            .filter(|ip_address| matches!(ip_address, IpAddr::V6(_)))
+            .min_by_key(ipv6_scope_rank)
             .ok_or(Error::LocalIpAddressNotFound)
     }
 
     // A catch-all case to error if not implemented for OS
-    #[cfg(not(any(
-        target_os = "linux",
-        target_os = "windows",
-        target_os = "macos",
-        target_os = "freebsd",
-        target_os = "openbsd",
-        target_os = "netbsd",
-        target_os = "dragonfly",
-        target_os = "android",
-        target_os = "ios",
-    )))]
+    #[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
     {
         Err(Error::PlatformNotSupported(
             std::env::consts::OS.to_string(),
@@ -236,95 +479,1809 @@ pub fn local_ipv6() -> Result<IpAddr, Error> {
     }
 }
 
-// A catch-all function to error if not implemented for OS
-#[cfg(not(any(
-    target_os = "linux",
-    target_os = "windows",
-    target_os = "macos",
-    target_os = "freebsd",
-    target_os = "openbsd",
-    target_os = "netbsd",
-    target_os = "dragonfly",
-    target_os = "android",
-    target_os = "ios",
-)))]
-pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
-    Err(Error::PlatformNotSupported(
-        std::env::consts::OS.to_string(),
-    ))
+/// Retrieves the local IP address of the machine considering both the `AF_INET`
+/// and the `AF_INET6` families.
+///
+/// On Linux and Windows the metrics of the default routes of both families are
+/// compared and the local IP address of the family with the best one is
+/// returned, IPv6 is preferred when both have the same metric. This heuristic
+/// may not match the family used by new outbound connections, which is chosen
+/// by the RFC 6724 destination address selection of `getaddrinfo` regardless
+/// of route metrics.
+///
+/// On BSD-based systems route metrics are not available, the local IPv4 address
+/// is returned if any, otherwise the local IPv6 address.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::best_local_ip;
+///
+/// if let Ok(ip) = best_local_ip() {
+///     println!("Best local IP address: {:?}", ip);
+/// }
+/// ```
+pub fn best_local_ip() -> Result<IpAddr, Error> {
+    #[cfg(netlink_backend)]
+    {
+        crate::linux::best_local_ip()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::windows::best_local_ip()
+    }
+
+    #[cfg(not(any(netlink_backend, target_os = "windows")))]
+    {
+        match local_ip() {
+            Err(Error::LocalIpAddressNotFound) => local_ipv6(),
+            result => result,
+        }
+    }
 }
 
-mod tests {
-    #[allow(unused_imports)]
-    use super::*;
+/// Retrieves the preferred IPv6 address, excluding loopback addresses when
+/// `exclude_loopback` is `true` and link-local addresses (`fe80::/10`) when
+/// `exclude_link_local` is `true`.
+///
+/// The address is found by enumerating the system's network interfaces, unlike
+/// [`local_ipv6`] which relies on the routing table on Linux and Windows.
+/// Addresses on interfaces which are not up and running are always skipped.
+///
+/// Global addresses are preferred over unique-local addresses (`fc00::/7`),
+/// which are preferred over link-local addresses, which are preferred over
+/// loopback addresses. Among addresses of the same scope the primary address of
+/// an interface is preferred, otherwise the enumeration order is kept.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::local_ipv6_opts;
+///
+/// // Neither loopback nor link-local
+/// if let Ok(ip) = local_ipv6_opts(true, true) {
+///     println!("This is my local IPv6 address: {:?}", ip);
+/// }
+/// ```
+pub fn local_ipv6_opts(exclude_loopback: bool, exclude_link_local: bool) -> Result<IpAddr, Error> {
+    let mut candidates: Vec<InterfaceAddr> = list_afinet_netifas_info()?
+        .into_iter()
+        .filter(|ifa| {
+            let is_loopback = ifa.is_loopback || ifa.addr.is_loopback();
 
-    #[test]
-    #[cfg(target_os = "linux")]
-    fn find_local_ip() {
-        let my_local_ip = local_ip();
+            ifa.addr.is_ipv6()
+                && ifa.is_up
+                && !ifa.is_mobile_data()
+                && !(exclude_loopback && is_loopback)
+                && !(exclude_link_local && is_link_local(&ifa.addr))
+        })
+        .collect();
 
-        assert!(matches!(my_local_ip, Ok(IpAddr::V4(_))));
-        println!("Linux 'local_ip': {:?}", my_local_ip);
-    }
+    candidates.sort_by_key(|ifa| {
+        let scope = if ifa.is_loopback {
+            ipv6_scope_rank(&IpAddr::V6(Ipv6Addr::LOCALHOST))
+        } else {
+            ipv6_scope_rank(&ifa.addr)
+        };
 
-    #[test]
-    #[cfg(any(
-        target_os = "freebsd",
-        target_os = "openbsd",
-        target_os = "netbsd",
-        target_os = "dragonfly",
-        target_os = "macos",
-        target_os = "android",
-        target_os = "ios",
-    ))]
-    fn find_local_ip() {
-        let my_local_ip = local_ip();
+        (scope, !ifa.is_primary)
+    });
 
-        assert!(matches!(my_local_ip, Ok(IpAddr::V4(_))));
-        println!("Unix 'local_ip': {:?}", my_local_ip);
-    }
+    candidates
+        .first()
+        .map(|ifa| ifa.addr)
+        .ok_or(Error::LocalIpAddressNotFound)
+}
 
-    #[test]
-    #[cfg(target_os = "windows")]
-    fn find_local_ip() {
-        let my_local_ip = local_ip();
+/// Retrieves both a local IPv4 and a local IPv6 address of the machine with a
+/// single enumeration of the system's network interfaces, `None` is returned for
+/// a family without a suitable address.
+///
+/// The IPv4 address is the primary address of the first non-loopback interface
+/// which is up and running. The IPv6 address is picked among non-loopback
+/// addresses as [`local_ipv6_opts`] does.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::local_ip_pair;
+///
+/// let (ipv4, ipv6) = local_ip_pair().unwrap();
+///
+/// println!("IPv4: {:?}, IPv6: {:?}", ipv4, ipv6);
+/// ```
+pub fn local_ip_pair() -> Result<(Option<Ipv4Addr>, Option<Ipv6Addr>), Error> {
+    let ifas: Vec<InterfaceAddr> = list_afinet_netifas_info()?
+        .into_iter()
+        .filter(|ifa| {
+            ifa.is_up && !ifa.is_loopback && !ifa.addr.is_loopback() && !ifa.is_mobile_data()
+        })
+        .collect();
 
-        assert!(matches!(my_local_ip, Ok(IpAddr::V4(_))));
-        println!("Windows 'local_ip': {:?}", my_local_ip);
+    let ipv4 = ifas.iter().find_map(|ifa| match ifa.addr {
+        IpAddr::V4(addr) if ifa.is_primary => Some(addr),
+        _ => None,
+    });
+    let ipv6 = ifas
+        .iter()
+        .filter(|ifa| ifa.addr.is_ipv6())
+        .min_by_key(|ifa| (ipv6_scope_rank(&ifa.addr), !ifa.is_primary))
+        .and_then(|ifa| match ifa.addr {
+            IpAddr::V6(addr) => Some(addr),
+            IpAddr::V4(_) => None,
+        });
+
+    Ok((ipv4, ipv6))
+}
+
+/// Ranks an address by scope, lower ranks being preferred when picking a local
+/// IPv6 address
+fn ipv6_scope_rank(addr: &IpAddr) -> u8 {
+    match addr_scope(addr) {
+        AddrScope::Global => 0,
+        AddrScope::Private => 1,
+        AddrScope::LinkLocal => 2,
+        AddrScope::Loopback => 3,
     }
+}
 
-    #[test]
-    #[cfg(target_os = "linux")]
-    fn find_network_interfaces() {
-        let network_interfaces = list_afinet_netifas();
+/// Determines if an address is an IPv6 link-local address (`fe80::/10`)
+fn is_link_local(addr: &IpAddr) -> bool {
+    // `Ipv6Addr::is_unicast_link_local` requires Rust 1.84
+    match addr {
+        IpAddr::V6(addr) => (addr.segments()[0] & 0xffc0) == 0xfe80,
+        IpAddr::V4(_) => false,
+    }
+}
 
-        assert!(network_interfaces.is_ok());
-        assert!(!network_interfaces.unwrap().is_empty());
+/// Removes exact `(name, ip)` duplicates lazily, keeping the first occurrence of
+/// each pair so the order reported by the OS is preserved. Distinct addresses of
+/// a same interface are kept.
+#[cfg(any(netlink_backend, getifaddrs_backend, target_os = "windows"))]
+fn dedup_netifas<I>(ifas: I) -> impl Iterator<Item = (String, IpAddr)>
+where
+    I: IntoIterator<Item = (String, IpAddr)>,
+{
+    let mut seen = std::collections::HashSet::new();
+
+    ifas.into_iter()
+        .filter(move |(name, ip)| seen.insert((name.clone(), *ip)))
+}
+
+/// Builds a socket address for an IPv6 address reachable through the network
+/// interface with the given `name`, setting the interface index as scope id.
+///
+/// Link-local IPv6 addresses (`fe80::/10`) are only meaningful along with the
+/// interface they are reachable through, the resulting `SocketAddrV6` can be
+/// used with `TcpStream::connect` or `UdpSocket::send_to` directly.
+///
+/// On Windows `name` refers to the adapter's friendly name.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::net::{Ipv6Addr, TcpStream};
+/// use local_ip_address::scoped_socket_addr;
+///
+/// let addr = scoped_socket_addr("eth0", "fe80::1".parse().unwrap(), 8080).unwrap();
+/// let stream = TcpStream::connect(addr);
+/// ```
+pub fn scoped_socket_addr(name: &str, addr: Ipv6Addr, port: u16) -> Result<SocketAddrV6, Error> {
+    let scope_id = interface_index(name)?;
+
+    Ok(SocketAddrV6::new(addr, port, 0, scope_id))
+}
+
+/// Retrieves the addresses of the system's network interfaces belonging to both
+/// socket address families `AF_INET` and `AF_INET6`, grouped by interface name.
+///
+/// Addresses of each interface keep the order in which they are reported by the
+/// operative system, interfaces are sorted by name.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::netifas_grouped;
+///
+/// if let Ok(netifas) = netifas_grouped() {
+///     for (name, addrs) in netifas.iter() {
+///         println!("{}:\t{:?}", name, addrs);
+///     }
+/// }
+/// ```
+pub fn netifas_grouped() -> Result<BTreeMap<String, Vec<IpAddr>>, Error> {
+    let mut grouped: BTreeMap<String, Vec<IpAddr>> = BTreeMap::new();
+
+    for (name, addr) in list_afinet_netifas()? {
+        grouped.entry(name).or_default().push(addr);
     }
 
-    #[test]
-    #[cfg(any(
-        target_os = "freebsd",
-        target_os = "openbsd",
-        target_os = "netbsd",
-        target_os = "dragonfly",
-        target_os = "macos",
-        target_os = "android",
-        target_os = "ios",
-    ))]
-    fn find_network_interfaces() {
-        let network_interfaces = list_afinet_netifas();
+    Ok(grouped)
+}
 
-        assert!(network_interfaces.is_ok());
-        assert!(!network_interfaces.unwrap().is_empty());
+/// Finds the addresses assigned to more than one network interface, which is
+/// usually a sign of a misconfiguration, along with the names of the interfaces
+/// holding each of them.
+///
+/// Linux alias interfaces (`eth0:1`) share the base interface of their label, an
+/// address held by an interface and its aliases is not a duplicate. Link-local
+/// addresses are only meaningful on their own link and are skipped as well.
+/// Addresses are sorted in ascending order.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::find_duplicate_addresses;
+///
+/// for (addr, names) in find_duplicate_addresses().unwrap() {
+///     println!("{:?} is assigned to {}", addr, names.join(", "));
+/// }
+/// ```
+pub fn find_duplicate_addresses() -> Result<Vec<(IpAddr, Vec<String>)>, Error> {
+    Ok(duplicate_addresses(list_afinet_netifas()?))
+}
+
+fn duplicate_addresses(netifas: Vec<(String, IpAddr)>) -> Vec<(IpAddr, Vec<String>)> {
+    let mut by_addr: BTreeMap<IpAddr, Vec<String>> = BTreeMap::new();
+
+    for (name, addr) in netifas {
+        let is_link_local = match addr {
+            IpAddr::V4(addr) => addr.is_link_local(),
+            IpAddr::V6(_) => is_link_local(&addr),
+        };
+
+        if is_link_local {
+            continue;
+        }
+
+        let names = by_addr.entry(addr).or_default();
+
+        if !names.contains(&name) {
+            names.push(name);
+        }
     }
 
-    #[test]
-    #[cfg(target_os = "windows")]
-    fn find_network_interfaces() {
-        let network_interfaces = list_afinet_netifas();
+    by_addr
+        .into_iter()
+        .filter(|(_, names)| {
+            let base_name = |name: &String| name.split(':').next().unwrap_or_default().to_owned();
+            let first = base_name(&names[0]);
 
-        assert!(network_interfaces.is_ok());
-        assert!(!network_interfaces.unwrap().is_empty());
+            names.iter().any(|name| base_name(name) != first)
+        })
+        .collect()
+}
+
+/// Retrieves the addresses of the system's network interfaces belonging to both
+/// socket address families `AF_INET` and `AF_INET6` just as [`list_afinet_netifas`]
+/// does, keeping the entries for which `pred` returns `true`.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_afinet_netifas_filtered;
+///
+/// // Non-loopback IPv4 addresses of Ethernet interfaces
+/// let netifas = list_afinet_netifas_filtered(|name, addr| {
+///     name.starts_with("eth") && addr.is_ipv4() && !addr.is_loopback()
+/// });
+///
+/// if let Ok(netifas) = netifas {
+///     for (name, addr) in netifas.iter() {
+///         println!("{}:\t{:?}", name, addr);
+///     }
+/// }
+/// ```
+pub fn list_afinet_netifas_filtered<F>(pred: F) -> Result<Vec<(String, IpAddr)>, Error>
+where
+    F: Fn(&str, &IpAddr) -> bool,
+{
+    let netifas = list_afinet_netifas()?;
+
+    Ok(netifas
+        .into_iter()
+        .filter(|(name, addr)| pred(name, addr))
+        .collect())
+}
+
+/// Retrieves the addresses of the system's network interfaces whose name matches
+/// the glob `pattern`, paired with the interface name.
+///
+/// The pattern supports `*`, matching any sequence of characters, and `?`,
+/// matching a single character. Any other character matches itself. On
+/// Windows the pattern is matched case-insensitively against the adapter's
+/// friendly name.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_afinet_netifas_matching;
+///
+/// if let Ok(netifas) = list_afinet_netifas_matching("eth*") {
+///     for (name, addr) in netifas.iter() {
+///         println!("{}:\t{:?}", name, addr);
+///     }
+/// }
+/// ```
+pub fn list_afinet_netifas_matching(pattern: &str) -> Result<Vec<(String, IpAddr)>, Error> {
+    list_afinet_netifas_filtered(|name, _| glob_match(pattern, name))
+}
+
+/// Retrieves the globally reachable addresses of the system's network interfaces,
+/// paired with the interface name.
+///
+/// Loopback, link-local and private addresses are discarded, refer to
+/// [`addr_scope`] for details on how each address is classified.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_global_afinet_netifas;
+///
+/// if let Ok(netifas) = list_global_afinet_netifas() {
+///     for (name, addr) in netifas.iter() {
+///         println!("{}:\t{:?}", name, addr);
+///     }
+/// }
+/// ```
+pub fn list_global_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
+    list_afinet_netifas_filtered(|_, addr| addr_scope(addr) == AddrScope::Global)
+}
+
+/// Retrieves the addresses of the network interface with the given `name`.
+///
+/// Names are compared as the operative system does: Windows adapters' friendly
+/// names are compared case-insensitively while names are case-sensitive on
+/// other platforms.
+///
+/// Returns `Error::InterfaceNotFound` when no network interface with the given
+/// `name` holds an address.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::find_interface;
+///
+/// if let Ok(ip_addresses) = find_interface("en0") {
+///     for ip in ip_addresses.iter() {
+///         println!("en0:\t{:?}", ip);
+///     }
+/// }
+/// ```
+pub fn find_interface(name: &str) -> Result<Vec<IpAddr>, Error> {
+    let ip_addresses: Vec<IpAddr> =
+        list_afinet_netifas_filtered(|ifname, _| same_interface_name(ifname, name))?
+            .into_iter()
+            .map(|(_, addr)| addr)
+            .collect();
+
+    if ip_addresses.is_empty() {
+        return Err(Error::InterfaceNotFound(name.to_string()));
+    }
+
+    Ok(ip_addresses)
+}
+
+/// Retrieves the addresses of the system's network interfaces whose name matches
+/// the glob `pattern`, paired with the interface name.
+///
+/// This is an alias of [`list_afinet_netifas_matching`], a prefix is matched
+/// with a trailing `*` (e.g. `eth*`).
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::find_interfaces_matching;
+///
+/// if let Ok(netifas) = find_interfaces_matching("eth*") {
+///     for (name, addr) in netifas.iter() {
+///         println!("{}:\t{:?}", name, addr);
+///     }
+/// }
+/// ```
+pub fn find_interfaces_matching(pattern: &str) -> Result<Vec<(String, IpAddr)>, Error> {
+    list_afinet_netifas_matching(pattern)
+}
+
+/// Determines if two network interface names are the same, Windows friendly
+/// names are case-insensitive
+fn same_interface_name(a: &str, b: &str) -> bool {
+    if cfg!(target_os = "windows") {
+        a.to_lowercase() == b.to_lowercase()
+    } else {
+        a == b
+    }
+}
+
+/// Matches `name` against a glob `pattern` supporting `*` and `?` wildcards,
+/// case-insensitively on Windows as friendly names are case-insensitive
+fn glob_match(pattern: &str, name: &str) -> bool {
+    #[cfg(target_os = "windows")]
+    let (pattern, name) = (pattern.to_lowercase(), name.to_lowercase());
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and of the name when it was found,
+    // to backtrack when the rest of the pattern doesn't match
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                // Let the last `*` match one more character
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Retrieves the addresses of the system's network interfaces which are up and
+/// running, paired with the interface name.
+///
+/// Addresses of interfaces which are configured but not operational, such as a
+/// network card without a cable plugged in, are skipped. Refer to
+/// [`InterfaceAddr::is_up`] for details on each platform.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_up_afinet_netifas;
+///
+/// if let Ok(netifas) = list_up_afinet_netifas() {
+///     for (name, addr) in netifas.iter() {
+///         println!("{}:\t{:?}", name, addr);
+///     }
+/// }
+/// ```
+pub fn list_up_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
+    let ifas = list_afinet_netifas_info()?;
+
+    Ok(ifas
+        .into_iter()
+        .filter(|ifa| ifa.is_up)
+        .map(|ifa| (ifa.name, ifa.addr))
+        .collect())
+}
+
+/// Retrieves the addresses of the system's network interfaces which support
+/// multicast, paired with the interface name.
+///
+/// On Windows loopback adapters and adapters flagged with
+/// `IP_ADAPTER_NO_MULTICAST` are not considered multicast capable.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::multicast_capable_netifas;
+///
+/// if let Ok(netifas) = multicast_capable_netifas() {
+///     for (name, addr) in netifas.iter() {
+///         println!("{}:\t{:?}", name, addr);
+///     }
+/// }
+/// ```
+pub fn multicast_capable_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
+    let ifas = list_afinet_netifas_info()?;
+
+    Ok(ifas
+        .into_iter()
+        .filter(|ifa| ifa.is_multicast)
+        .map(|ifa| (ifa.name, ifa.addr))
+        .collect())
+}
+
+/// Retrieves the network address of each address of the system's network
+/// interfaces, this is the address masked with its network prefix (e.g.
+/// `192.168.1.0` for `192.168.1.10/24`), paired with the interface name.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_networks;
+///
+/// if let Ok(networks) = list_networks() {
+///     for (name, network) in networks.iter() {
+///         println!("{}:\t{:?}", name, network);
+///     }
+/// }
+/// ```
+pub fn list_networks() -> Result<Vec<(String, IpAddr)>, Error> {
+    let ifas = list_afinet_netifas_info()?;
+
+    Ok(ifas
+        .into_iter()
+        .map(|ifa| {
+            let network = ifa.network();
+            (ifa.name, network)
+        })
+        .collect())
+}
+
+/// Retrieves the netmask of each address of the system's network interfaces as
+/// an address of the matching family (e.g. `255.255.255.0` for `192.168.1.10/24`),
+/// paired with the interface name.
+///
+/// The netmask is built from the network prefix reported by the platform:
+/// `ifa_netmask` with `getifaddrs`, `ifa_prefixlen` with Netlink and
+/// `OnLinkPrefixLength` on Windows.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_netmasks;
+///
+/// if let Ok(netmasks) = list_netmasks() {
+///     for (name, netmask) in netmasks.iter() {
+///         println!("{}:\t{:?}", name, netmask);
+///     }
+/// }
+/// ```
+pub fn list_netmasks() -> Result<Vec<(String, IpAddr)>, Error> {
+    let ifas = list_afinet_netifas_info()?;
+
+    Ok(ifas
+        .into_iter()
+        .map(|ifa| {
+            let netmask = ifa.netmask();
+            (ifa.name, netmask)
+        })
+        .collect())
+}
+
+/// Retrieves the addresses of the system's network interfaces belonging to both
+/// socket address families `AF_INET` and `AF_INET6` just as [`list_afinet_netifas`]
+/// does, each paired with its netmask as an address of the same family.
+///
+/// Netmasks are built as described in [`list_netmasks`]. Point-to-point
+/// interfaces and host addresses report a `/32` (`255.255.255.255`) or `/128`
+/// netmask.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_afinet_netifas_with_netmask;
+///
+/// if let Ok(netifas) = list_afinet_netifas_with_netmask() {
+///     for (name, addr, netmask) in netifas.iter() {
+///         println!("{}:\t{:?}\t{:?}", name, addr, netmask);
+///     }
+/// }
+/// ```
+pub fn list_afinet_netifas_with_netmask() -> Result<Vec<(String, IpAddr, IpAddr)>, Error> {
+    let ifas = list_afinet_netifas_info()?;
+
+    Ok(ifas
+        .into_iter()
+        .map(|ifa| {
+            let netmask = ifa.netmask();
+            (ifa.name, ifa.addr, netmask)
+        })
+        .collect())
+}
+
+/// Retrieves the addresses of the system's network interfaces belonging to both
+/// socket address families `AF_INET` and `AF_INET6` just as [`list_afinet_netifas`]
+/// does, each paired with the index of its interface.
+///
+/// The index is the one taken by `setsockopt` options such as `IP_MULTICAST_IF`
+/// or `IPV6_MULTICAST_IF`, refer to [`NetworkInterface::index`] for details on
+/// each platform. Addresses whose interface index is unknown are skipped.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_afinet_netifas_with_index;
+///
+/// if let Ok(netifas) = list_afinet_netifas_with_index() {
+///     for (name, index, addr) in netifas.iter() {
+///         println!("{} ({}):\t{:?}", name, index, addr);
+///     }
+/// }
+/// ```
+pub fn list_afinet_netifas_with_index() -> Result<Vec<(String, u32, IpAddr)>, Error> {
+    let ifas = list_network_interfaces()?;
+
+    Ok(ifas
+        .into_iter()
+        .filter_map(|ifa| Some((ifa.name, ifa.index?, ifa.addr)))
+        .collect())
+}
+
+/// Retrieves the link-local IPv6 address (`fe80::/10`) of the network interface
+/// with the given `name` along with its scope id, which is the index of the
+/// interface. On Windows `name` is the adapter's friendly name, compared
+/// case-insensitively.
+///
+/// Returns `Error::InterfaceNotFound` if no interface with such name exists, and
+/// `Error::LocalIpAddressNotFound` if the interface has no link-local address.
+///
+/// # Example
+///
+/// ```no_run
+/// use local_ip_address::link_local_ipv6;
+///
+/// let (addr, scope_id) = link_local_ipv6("eth0").unwrap();
+///
+/// println!("{}%{}", addr, scope_id);
+/// ```
+pub fn link_local_ipv6(name: &str) -> Result<(Ipv6Addr, u32), Error> {
+    let scope_id = interface_index(name)?;
+
+    list_afinet_netifas()?
+        .into_iter()
+        .filter(|(ifname, addr)| same_interface_name(ifname, name) && is_link_local(addr))
+        .find_map(|(_, addr)| match addr {
+            IpAddr::V6(addr) => Some((addr, scope_id)),
+            IpAddr::V4(_) => None,
+        })
+        .ok_or(Error::LocalIpAddressNotFound)
+}
+
+/// Retrieves the global IPv6 addresses of the system's network interfaces,
+/// each one decomposed into its network prefix (upper 64 bits) and interface
+/// identifier (lower 64 bits) as done by [`split_ipv6`].
+///
+/// Loopback, link-local (`fe80::/10`) and unspecified addresses are skipped.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_ipv6_components;
+///
+/// if let Ok(addrs) = list_ipv6_components() {
+///     for (name, addr, prefix, iid) in addrs.iter() {
+///         println!("{}:\t{} prefix={:016x} iid={:016x}", name, addr, prefix, iid);
+///     }
+/// }
+/// ```
+pub fn list_ipv6_components() -> Result<Vec<(String, Ipv6Addr, u64, u64)>, Error> {
+    let netifas = list_afinet_netifas()?;
+
+    Ok(netifas
+        .into_iter()
+        .filter_map(|(name, addr)| match addr {
+            IpAddr::V6(addr)
+                if !addr.is_loopback()
+                    && !addr.is_unspecified()
+                    && !is_link_local(&IpAddr::V6(addr)) =>
+            {
+                let (prefix, iid) = split_ipv6(&addr);
+                Some((name, addr, prefix, iid))
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+/// Determines if a TCP socket can be bound to the given local address and port,
+/// which confirms an enumerated address is actually usable. For instance a
+/// tentative IPv6 address is reported by the operative system but can't be
+/// bound until Duplicate Address Detection completes.
+///
+/// The socket is closed right after binding. On non-Windows systems
+/// `SO_REUSEADDR` is set before binding so sockets lingering in `TIME_WAIT` don't
+/// make the address look unusable, the option is not set on Windows where it
+/// allows binding ports actively in use. A `port` of `0` checks the address
+/// alone.
+///
+/// Returns `Ok(false)` when the address is not available, the port is in use or
+/// binding is not permitted.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::{can_bind, list_afinet_netifas};
+///
+/// if let Ok(netifas) = list_afinet_netifas() {
+///     for (name, addr) in netifas.iter() {
+///         println!("{}:\t{:?}\tusable: {:?}", name, addr, can_bind(*addr, 0));
+///     }
+/// }
+/// ```
+pub fn can_bind(addr: IpAddr, port: u16) -> Result<bool, Error> {
+    match TcpListener::bind((addr, port)) {
+        Ok(_) => Ok(true),
+        Err(err)
+            if matches!(
+                err.kind(),
+                ErrorKind::AddrNotAvailable | ErrorKind::AddrInUse | ErrorKind::PermissionDenied
+            ) =>
+        {
+            Ok(false)
+        }
+        Err(err) => Err(Error::Io(err)),
+    }
+}
+
+/// Retrieves the name of the network interface holding the given local address.
+///
+/// Returns `Error::LocalIpAddressNotFound` if the address is not assigned to any
+/// of the system's network interfaces. `IpAddr` carries no scope id, therefore
+/// a link-local IPv6 address assigned to several interfaces resolves to the
+/// first interface holding it.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::{interface_for_ip, local_ip};
+///
+/// if let Ok(ip) = local_ip() {
+///     if let Ok(name) = interface_for_ip(ip) {
+///         println!("Bound to {} ({})", ip, name);
+///     }
+/// }
+/// ```
+pub fn interface_for_ip(addr: IpAddr) -> Result<String, Error> {
+    list_afinet_netifas()?
+        .into_iter()
+        .find(|(_, ifaddr)| *ifaddr == addr)
+        .map(|(name, _)| name)
+        .ok_or(Error::LocalIpAddressNotFound)
+}
+
+/// Retrieves the addresses of the DNS servers configured on the system.
+///
+/// On Windows the DNS servers of the adapters which are up are read from the
+/// adapters table, servers configured on several adapters are listed once. On
+/// Linux and other Unix systems the `nameserver` entries of `/etc/resolv.conf`
+/// are parsed, `Error::PlatformNotSupported` is returned when the file doesn't
+/// exist (e.g. on Android).
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::dns_servers;
+///
+/// if let Ok(dns_servers) = dns_servers() {
+///     for dns_server in dns_servers.iter() {
+///         println!("DNS server: {:?}", dns_server);
+///     }
+/// }
+/// ```
+pub fn dns_servers() -> Result<Vec<IpAddr>, Error> {
+    #[cfg(any(netlink_backend, getifaddrs_backend))]
+    {
+        crate::resolv::dns_server_addresses()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        crate::windows::dns_server_addresses()
+    }
+
+    // A catch-all case to error if not implemented for OS
+    #[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+    {
+        Err(Error::PlatformNotSupported(
+            std::env::consts::OS.to_string(),
+        ))
+    }
+}
+
+/// Retrieves the name of the strategy used to gather network interfaces on the
+/// platform this crate was compiled for.
+///
+/// - `"netlink"` on Linux
+/// - `"getifaddrs"` on BSD-based systems, macOS, Android, iOS, AIX, illumos
+///   and Solaris, as well as on Linux with the `linux-getifaddrs` feature or
+///   without the `netlink` feature
+/// - `"iphlpapi"` on Windows
+/// - `"unsupported"` on any other platform
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::strategy;
+///
+/// println!("Network interfaces are gathered through {}", strategy());
+/// ```
+pub fn strategy() -> &'static str {
+    #[cfg(netlink_backend)]
+    {
+        "netlink"
+    }
+
+    #[cfg(getifaddrs_backend)]
+    {
+        "getifaddrs"
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        "iphlpapi"
+    }
+
+    // A catch-all case to report if not implemented for OS
+    #[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+    {
+        "unsupported"
+    }
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn list_afinet_netifas_with_primary() -> Result<Vec<(String, IpAddr, bool)>, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn list_network_interfaces() -> Result<Vec<NetworkInterface>, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn list_afinet_netifas_info() -> Result<Vec<InterfaceAddr>, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn list_afinet_netifas_family(_: AddrFamily) -> Result<Vec<(String, IpAddr)>, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn iter_afinet_netifas() -> Result<impl Iterator<Item = (String, IpAddr)>, Error> {
+    Err::<std::iter::Empty<(String, IpAddr)>, Error>(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn local_broadcast_ip() -> Result<IpAddr, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn broadcast_ip_on(_: &str) -> Result<IpAddr, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn addresses_for_index(_: u32) -> Result<Vec<IpAddr>, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn list_afinet_netifas_lenient() -> Result<(Vec<(String, IpAddr)>, usize), Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn list_afinet_netifas_v6_flowinfo() -> Result<Vec<(String, Ipv6Addr, u32)>, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+pub fn address_family_counts() -> Result<(usize, usize), Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+/// Retrieves the time of the last change of each of the system's network
+/// interfaces.
+///
+/// Only BSD-based systems providing `ifi_lastchange` through `getifaddrs`
+/// (macOS, iOS, FreeBSD and DragonFly) report it, `Error::PlatformNotSupported`
+/// is returned on any other platform.
+#[cfg(not(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+)))]
+pub fn interface_last_change() -> Result<Vec<(String, std::time::SystemTime)>, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+/// Determines if IPv6 is administratively disabled on the network interface
+/// with the given name.
+///
+/// Only Linux exposes this through the `disable_ipv6` sysctl,
+/// `Error::PlatformNotSupported` is returned on any other platform.
+#[cfg(not(netlink_backend))]
+pub fn ipv6_disabled(_: &str) -> Result<bool, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+/// Netlink Route client which reuses a single socket across queries.
+///
+/// Only the Netlink backend provides it, [`NetlinkClient::new`] returns
+/// `Error::PlatformNotSupported` when network interfaces are gathered through
+/// `getifaddrs` on Linux.
+#[cfg(all(target_os = "linux", not(netlink_backend)))]
+pub struct NetlinkClient {
+    _private: (),
+}
+
+#[cfg(all(target_os = "linux", not(netlink_backend)))]
+impl NetlinkClient {
+    /// Returns `Error::PlatformNotSupported`, no Netlink socket is available
+    pub fn new() -> Result<NetlinkClient, Error> {
+        Err(Error::PlatformNotSupported(
+            std::env::consts::OS.to_string(),
+        ))
+    }
+
+    /// Retrieves the local IPv4 address for this system, refer to [`local_ip`]
+    pub fn local_ip(&mut self) -> Result<IpAddr, Error> {
+        local_ip()
+    }
+
+    /// Retrieves the local IPv6 address for this system, refer to [`local_ipv6`]
+    pub fn local_ipv6(&mut self) -> Result<IpAddr, Error> {
+        local_ipv6()
+    }
+
+    /// Perform a search over the system's network interfaces, refer to
+    /// [`list_afinet_netifas`]
+    pub fn list_afinet_netifas(&mut self) -> Result<Vec<(String, IpAddr)>, Error> {
+        list_afinet_netifas()
+    }
+}
+
+/// Retrieves the local IP address the system would use as source address when
+/// sending packets to `dest`.
+///
+/// Only the Netlink backend provides it, `Error::PlatformNotSupported` is
+/// returned when network interfaces are gathered through `getifaddrs` on Linux,
+/// [`local_ip_to`] is available on every platform.
+#[cfg(all(target_os = "linux", not(netlink_backend)))]
+pub fn local_ip_toward(_: IpAddr) -> Result<IpAddr, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+/// Performs the `RTM_GETADDR` dump used to list network interfaces and returns
+/// the raw bytes of the kernel's response.
+///
+/// Only the Netlink backend provides it, `Error::PlatformNotSupported` is
+/// returned when network interfaces are gathered through `getifaddrs` on Linux.
+#[cfg(all(target_os = "linux", feature = "debug-netlink", not(netlink_backend)))]
+pub fn dump_netlink_addr_response() -> Result<Vec<u8>, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(
+    netlink_backend,
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd"
+)))]
+pub fn default_gateway() -> Result<IpAddr, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, target_os = "windows")))]
+pub fn default_gateway_v6() -> Result<IpAddr, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+// A catch-all function to error if not implemented for OS
+#[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+fn interface_index(_: &str) -> Result<u32, Error> {
+    Err(Error::PlatformNotSupported(
+        std::env::consts::OS.to_string(),
+    ))
+}
+
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    #[cfg(not(any(netlink_backend, getifaddrs_backend, target_os = "windows")))]
+    fn unsupported_platform_is_reported() {
+        assert!(matches!(local_ip(), Err(Error::PlatformNotSupported(_))));
+        assert!(matches!(local_ipv6(), Err(Error::PlatformNotSupported(_))));
+        assert!(matches!(
+            wait_for_local_ip(Duration::from_secs(60), Duration::from_secs(1)),
+            Err(Error::PlatformNotSupported(_))
+        ));
+        assert!(matches!(
+            list_afinet_netifas(),
+            Err(Error::PlatformNotSupported(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_local_ip() {
+        let my_local_ip = local_ip();
+
+        assert!(matches!(my_local_ip, Ok(IpAddr::V4(_))));
+        println!("Linux 'local_ip': {:?}", my_local_ip);
+    }
+
+    #[test]
+    #[cfg(any(
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly",
+        target_os = "macos",
+        target_os = "android",
+        target_os = "ios",
+        target_os = "aix",
+        target_os = "illumos",
+        target_os = "solaris",
+    ))]
+    fn find_local_ip() {
+        let my_local_ip = local_ip();
+
+        assert!(matches!(my_local_ip, Ok(IpAddr::V4(_))));
+        println!("Unix 'local_ip': {:?}", my_local_ip);
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn find_local_ip() {
+        let my_local_ip = local_ip();
+
+        assert!(matches!(my_local_ip, Ok(IpAddr::V4(_))));
+        println!("Windows 'local_ip': {:?}", my_local_ip);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_network_interfaces() {
+        let network_interfaces = list_afinet_netifas();
+
+        assert!(network_interfaces.is_ok());
+        assert!(!network_interfaces.unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(any(
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly",
+        target_os = "macos",
+        target_os = "android",
+        target_os = "ios",
+        target_os = "aix",
+        target_os = "illumos",
+        target_os = "solaris",
+    ))]
+    fn find_network_interfaces() {
+        let network_interfaces = list_afinet_netifas();
+
+        assert!(network_interfaces.is_ok());
+        assert!(!network_interfaces.unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn find_network_interfaces() {
+        let network_interfaces = list_afinet_netifas();
+
+        assert!(network_interfaces.is_ok());
+        assert!(!network_interfaces.unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn find_default_route_ip_addresses() {
+        let ip_addresses = list_default_route_ip_addresses().unwrap();
+
+        assert!(ip_addresses.contains(&local_ip().unwrap()));
+        assert!(ip_addresses
+            .iter()
+            .all(|ip| ip.is_ipv4() && !ip.is_loopback()));
+    }
+
+    #[test]
+    #[cfg(target_os = "windows")]
+    fn find_network_interfaces_with_adapter_addresses_flags() {
+        use windows_sys::Win32::NetworkManagement::IpHelper::{
+            GAA_FLAG_SKIP_DNS_SERVER, GAA_FLAG_SKIP_MULTICAST, GAA_FLAG_SKIP_UNICAST,
+        };
+
+        let options = WindowsOptions {
+            adapter_addresses_flags: GAA_FLAG_SKIP_DNS_SERVER | GAA_FLAG_SKIP_MULTICAST,
+            ..WindowsOptions::default()
+        };
+
+        assert_eq!(
+            list_afinet_netifas_info_with_options(&options).unwrap(),
+            list_afinet_netifas_info().unwrap()
+        );
+
+        // Unicast addresses are requested regardless of the flags
+        let options = WindowsOptions {
+            adapter_addresses_flags: GAA_FLAG_SKIP_UNICAST,
+            ..WindowsOptions::default()
+        };
+
+        assert!(!list_afinet_netifas_info_with_options(&options)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_loopback_network_interface() {
+        let network_interfaces = list_afinet_netifas_info().unwrap();
+
+        assert!(network_interfaces
+            .iter()
+            .any(|ifa| ifa.is_loopback && ifa.addr.is_loopback()));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn scoped_socket_addr_sets_scope_id() {
+        let addr = scoped_socket_addr("lo", Ipv6Addr::LOCALHOST, 8080).unwrap();
+
+        assert_eq!(addr.scope_id(), 1);
+        assert_eq!(addr.port(), 8080);
+        assert!(matches!(
+            scoped_socket_addr("doesnotexist0", Ipv6Addr::LOCALHOST, 8080),
+            Err(Error::InterfaceNotFound(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn group_network_interfaces() {
+        let network_interfaces = netifas_grouped().unwrap();
+
+        assert!(network_interfaces
+            .get("lo")
+            .map(|addrs| addrs.iter().any(|addr| addr.is_loopback()))
+            .unwrap_or(false));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_interface_for_ip() {
+        use std::net::Ipv4Addr;
+
+        assert_eq!(
+            interface_for_ip(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            Ok(String::from("lo"))
+        );
+        assert_eq!(
+            interface_for_ip(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 255))),
+            Err(Error::LocalIpAddressNotFound)
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn count_address_families() {
+        let (v4, v6) = address_family_counts().unwrap();
+        let ifas = list_afinet_netifas_info().unwrap();
+        let non_loopback = ifas.iter().filter(|ifa| !ifa.addr.is_loopback());
+
+        assert_eq!(v4 + v6, non_loopback.clone().count());
+        assert_eq!(v4, non_loopback.filter(|ifa| ifa.addr.is_ipv4()).count());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn linux_strategy() {
+        if cfg!(getifaddrs_backend) {
+            assert_eq!(strategy(), "getifaddrs");
+        } else {
+            assert_eq!(strategy(), "netlink");
+        }
+    }
+
+    #[test]
+    #[cfg(all(target_os = "linux", getifaddrs_backend))]
+    fn netlink_api_is_unsupported_with_getifaddrs() {
+        assert!(matches!(
+            NetlinkClient::new(),
+            Err(Error::PlatformNotSupported(_))
+        ));
+        assert!(matches!(
+            local_ip_toward(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1))),
+            Err(Error::PlatformNotSupported(_))
+        ));
+        assert!(matches!(
+            default_gateway(),
+            Err(Error::PlatformNotSupported(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_loopback_network() {
+        use std::net::Ipv4Addr;
+
+        let networks = list_networks().unwrap();
+
+        assert!(networks
+            .iter()
+            .any(|(name, network)| name == "lo"
+                && *network == IpAddr::V4(Ipv4Addr::new(127, 0, 0, 0))));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn loopback_is_not_multicast_capable() {
+        let netifas = multicast_capable_netifas().unwrap();
+
+        assert!(!netifas.iter().any(|(name, _)| name == "lo"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_loopback_netmask() {
+        use std::net::Ipv4Addr;
+
+        let netmasks = list_netmasks().unwrap();
+
+        assert!(netmasks
+            .iter()
+            .any(|(name, netmask)| name == "lo"
+                && *netmask == IpAddr::V4(Ipv4Addr::new(255, 0, 0, 0))));
+    }
+
+    fn _assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn public_types_are_send_sync() {
+        _assert_send_sync::<Error>();
+        _assert_send_sync::<InterfaceAddr>();
+        _assert_send_sync::<InterfaceKind>();
+        _assert_send_sync::<InterfaceFlags>();
+        _assert_send_sync::<DestAddr>();
+        _assert_send_sync::<AddrFamily>();
+        _assert_send_sync::<AddrScope>();
+        _assert_send_sync::<NetworkInterface>();
+        _assert_send_sync::<Snapshot>();
+        _assert_send_sync::<CachedInterfaces>();
+        _assert_send_sync::<Change>();
+        #[cfg(target_os = "windows")]
+        _assert_send_sync::<WindowsOptions>();
+        #[cfg(netlink_backend)]
+        _assert_send_sync::<NetlinkClient>();
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_local_ip_fast() {
+        let my_local_ip = local_ip_fast();
+
+        assert!(matches!(my_local_ip, Ok(IpAddr::V4(_))));
+        println!("Fast local IP address: {:?}", my_local_ip);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_local_ipv6_opts() {
+        assert_eq!(local_ipv6_opts(true, true), local_ipv6());
+        assert!(matches!(local_ipv6_opts(false, false), Ok(IpAddr::V6(_))));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn filter_network_interfaces() {
+        let netifas = list_afinet_netifas_filtered(|name, _| name == "lo").unwrap();
+
+        assert!(!netifas.is_empty());
+        assert!(netifas.iter().all(|(name, _)| name == "lo"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn list_global_network_interfaces() {
+        let netifas = list_global_afinet_netifas().unwrap();
+
+        assert!(netifas
+            .iter()
+            .all(|(_, addr)| addr_scope(addr) == AddrScope::Global));
+        assert!(!netifas.iter().any(|(name, _)| name == "lo"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_local_ip_detailed() {
+        let (name, ip) = local_ip_detailed().unwrap();
+
+        assert_eq!(Ok(ip), local_ip());
+        assert_eq!(Ok(name.clone()), interface_for_ip(ip));
+        assert!(list_network_interfaces()
+            .unwrap()
+            .iter()
+            .any(|netifa| netifa.name == name && netifa.addr == ip));
+    }
+
+    #[test]
+    fn bind_local_addresses() {
+        use std::net::Ipv4Addr;
+
+        let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        assert_eq!(can_bind(localhost, 0), Ok(true));
+
+        // Address reserved for documentation, not assigned to this system
+        let remote = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 123));
+        assert_eq!(can_bind(remote, 0), Ok(false));
+
+        let listener = TcpListener::bind((localhost, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert_eq!(can_bind(localhost, port), Ok(false));
+    }
+
+    #[test]
+    fn find_duplicates_across_interfaces() {
+        let netifas = vec![
+            (String::from("eth0"), "192.168.1.10".parse().unwrap()),
+            (String::from("eth0:1"), "192.168.1.10".parse().unwrap()),
+            (String::from("eth1"), "10.0.0.1".parse().unwrap()),
+            (String::from("wlan0"), "10.0.0.1".parse().unwrap()),
+            (String::from("eth1"), "fe80::1".parse().unwrap()),
+            (String::from("wlan0"), "fe80::1".parse().unwrap()),
+            (String::from("lo"), "127.0.0.1".parse().unwrap()),
+        ];
+
+        assert_eq!(
+            duplicate_addresses(netifas),
+            vec![(
+                "10.0.0.1".parse().unwrap(),
+                vec![String::from("eth1"), String::from("wlan0")]
+            )]
+        );
+    }
+
+    #[test]
+    fn match_glob_patterns() {
+        assert!(glob_match("eth*", "eth0"));
+        assert!(glob_match("eth*", "eth"));
+        assert!(glob_match("en?", "en0"));
+        assert!(!glob_match("en?", "en10"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("w*0", "wlp2s0"));
+        assert!(!glob_match("w*0", "wlp2s1"));
+        assert!(glob_match("*Ethernet*", "vEthernet (WSL)"));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(!glob_match("lo", "lo0"));
+        assert!(!glob_match("", "lo"));
+    }
+
+    #[test]
+    fn rank_ipv6_addresses_by_scope() {
+        let mut addrs: Vec<IpAddr> = ["fe80::1", "::1", "fd00::1", "2001:db8::1"]
+            .iter()
+            .map(|addr| addr.parse().unwrap())
+            .collect();
+
+        addrs.sort_by_key(ipv6_scope_rank);
+
+        assert_eq!(
+            addrs,
+            ["2001:db8::1", "fd00::1", "fe80::1", "::1"]
+                .iter()
+                .map(|addr| addr.parse::<IpAddr>().unwrap())
+                .collect::<Vec<IpAddr>>()
+        );
+    }
+
+    #[test]
+    fn compare_interface_names() {
+        assert!(same_interface_name("eth0", "eth0"));
+        assert!(!same_interface_name("eth0", "eth1"));
+        assert_eq!(
+            same_interface_name("Ethernet", "ethernet"),
+            cfg!(target_os = "windows")
+        );
+        assert_eq!(
+            glob_match("ether*", "Ethernet 2"),
+            cfg!(target_os = "windows")
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_addresses_for_index() {
+        let index = interface_index("lo").unwrap();
+        let addresses = addresses_for_index(index).unwrap();
+
+        assert!(addresses.contains(&IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        assert!(addresses.iter().all(|addr| addr.is_loopback()));
+        assert!(addresses_for_index(u32::MAX).unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn wait_for_local_ip_until_timeout() {
+        let timeout = Duration::from_millis(50);
+
+        assert_eq!(
+            wait_for_local_ip(timeout, Duration::from_millis(10)),
+            local_ip()
+        );
+        assert_eq!(wait_for_local_ip(Duration::ZERO, timeout), local_ip());
+    }
+
+    #[test]
+    fn retry_until_returns_last_error() {
+        let mut attempts = 0;
+        let result: Result<(), Error> =
+            retry_until(Duration::from_millis(50), Duration::ZERO, || {
+                attempts += 1;
+                Err(Error::StrategyError(format!("attempt {}", attempts)))
+            });
+
+        assert_eq!(
+            result,
+            Err(Error::StrategyError(format!("attempt {}", attempts)))
+        );
+        // a zero poll is raised to `MIN_POLL` rather than spinning
+        assert!((2..=6).contains(&attempts));
+
+        let mut attempts = 0;
+        let result = retry_until(Duration::from_secs(60), Duration::ZERO, || {
+            attempts += 1;
+            Err::<(), _>(Error::PlatformNotSupported(String::from("os")))
+        });
+
+        assert_eq!(result, Err(Error::PlatformNotSupported(String::from("os"))));
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn dedup_exact_duplicates_only() {
+        let ifa = |name: &str, ip: &str| (String::from(name), ip.parse::<IpAddr>().unwrap());
+        let ifas = vec![
+            ifa("bond0", "192.168.1.10"),
+            ifa("bond0", "192.168.1.11"),
+            ifa("br0", "192.168.1.10"),
+            ifa("bond0", "192.168.1.10"),
+            ifa("bond0", "fe80::1"),
+        ];
+
+        assert_eq!(
+            dedup_netifas(ifas).collect::<Vec<_>>(),
+            vec![
+                ifa("bond0", "192.168.1.10"),
+                ifa("bond0", "192.168.1.11"),
+                ifa("br0", "192.168.1.10"),
+                ifa("bond0", "fe80::1"),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_dns_servers() {
+        match dns_servers() {
+            Ok(dns_servers) => println!("Linux 'dns_servers': {:?}", dns_servers),
+            Err(err) => assert!(matches!(err, Error::PlatformNotSupported(_))),
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn list_up_network_interfaces() {
+        let netifas = list_up_afinet_netifas().unwrap();
+        let all_netifas = list_afinet_netifas().unwrap();
+
+        assert!(netifas.iter().any(|(name, _)| name == "lo"));
+        assert!(netifas.iter().all(|netifa| all_netifas.contains(netifa)));
+    }
+
+    #[test]
+    #[cfg(netlink_backend)]
+    fn find_local_ip_toward() {
+        // routes towards the gateway and external addresses only exist on hosts
+        // with an IPv4 default route, which CI runners and containers may lack
+        match default_gateway() {
+            Ok(gateway) => {
+                assert_eq!(local_ip_toward(gateway), local_ip());
+                assert_eq!(
+                    local_ip_toward(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 0))),
+                    local_ip()
+                );
+            }
+            Err(err) => assert_eq!(err, Error::LocalIpAddressNotFound),
+        }
+        assert_eq!(
+            local_ip_toward(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            Ok(IpAddr::V4(Ipv4Addr::LOCALHOST))
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_interface_by_name() {
+        assert_eq!(
+            find_interface("lo"),
+            Ok(list_afinet_netifas_matching("lo")
+                .unwrap()
+                .into_iter()
+                .map(|(_, addr)| addr)
+                .collect())
+        );
+        assert_eq!(
+            find_interface("does-not-exist"),
+            Err(Error::InterfaceNotFound(String::from("does-not-exist")))
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn match_network_interfaces() {
+        let netifas = list_afinet_netifas_matching("l?").unwrap();
+
+        assert!(!netifas.is_empty());
+        assert!(netifas.iter().all(|(name, _)| name == "lo"));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn list_network_interfaces_by_family() {
+        let v4 = list_afinet_netifas_family(AddrFamily::Inet).unwrap();
+        let v6 = list_afinet_netifas_family(AddrFamily::Inet6).unwrap();
+
+        assert!(v4.iter().all(|(_, ip)| ip.is_ipv4()));
+        assert!(v6.iter().all(|(_, ip)| ip.is_ipv6()));
+        assert!(v4
+            .iter()
+            .any(|(_, ip)| *ip == IpAddr::V4(Ipv4Addr::LOCALHOST)));
+        assert_eq!(
+            list_afinet_netifas_family(AddrFamily::Unspec)
+                .unwrap()
+                .len(),
+            v4.len() + v6.len()
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_broadcast_ip_on_interface() {
+        assert_eq!(broadcast_ip_on("lo"), Err(Error::LocalIpAddressNotFound));
+        assert!(matches!(
+            broadcast_ip_on("does-not-exist0"),
+            Err(Error::InterfaceNotFound(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(netlink_backend)]
+    fn check_ipv6_disabled() {
+        // the sysctl is missing on kernels without IPv6 support
+        assert!(matches!(
+            ipv6_disabled("lo"),
+            Ok(_) | Err(Error::StrategyError(_))
+        ));
+        assert!(matches!(
+            ipv6_disabled("does-not-exist0"),
+            Err(Error::InterfaceNotFound(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_best_local_ip() {
+        let best_ip = best_local_ip();
+
+        assert!(matches!(
+            best_ip,
+            Ok(_) | Err(Error::LocalIpAddressNotFound)
+        ));
+        println!("Linux 'best_local_ip': {:?}", best_ip);
+    }
+
+    #[test]
+    #[cfg(netlink_backend)]
+    fn reuse_netlink_client() {
+        let mut client = NetlinkClient::new().unwrap();
+
+        assert_eq!(client.local_ip().ok(), local_ip().ok());
+        assert!(matches!(
+            client.local_ipv6(),
+            Ok(IpAddr::V6(_)) | Err(Error::LocalIpAddressNotFound)
+        ));
+        assert_eq!(
+            client.list_afinet_netifas().unwrap(),
+            list_afinet_netifas().unwrap()
+        );
+        assert_eq!(client.local_ip().ok(), local_ip().ok());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn iter_network_interfaces() {
+        // exact duplicates are only removed by the collecting functions
+        let netifas: Vec<(String, IpAddr)> =
+            dedup_netifas(iter_afinet_netifas().unwrap()).collect();
+
+        assert_eq!(netifas, list_afinet_netifas().unwrap());
+    }
+
+    #[test]
+    #[cfg(netlink_backend)]
+    fn find_default_gateway() {
+        let gateway = default_gateway();
+
+        assert!(matches!(
+            gateway,
+            Ok(IpAddr::V4(_)) | Err(Error::LocalIpAddressNotFound)
+        ));
+        println!("Linux 'default_gateway': {:?}", gateway);
+    }
+
+    #[test]
+    #[cfg(netlink_backend)]
+    fn find_default_gateway_v6() {
+        let gateway = default_gateway_v6();
+
+        assert!(matches!(
+            gateway,
+            Ok(IpAddr::V6(_)) | Err(Error::LocalIpAddressNotFound)
+        ));
+        println!("Linux 'default_gateway_v6': {:?}", gateway);
+    }
+
+    #[test]
+    fn find_local_ip_to_loopback() {
+        let dest = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        assert_eq!(local_ip_to(dest).unwrap(), dest);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_local_ip_pair() {
+        let (ipv4, ipv6) = local_ip_pair().unwrap();
+
+        assert!(!ipv4.iter().any(|ip| ip.is_loopback()));
+        assert!(!ipv6.iter().any(|ip| ip.is_loopback()));
+        println!("Linux 'local_ip_pair': {:?} {:?}", ipv4, ipv6);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn list_network_interfaces_leniently() {
+        let (netifas, skipped) = list_afinet_netifas_lenient().unwrap();
+
+        assert_eq!(skipped, 0);
+        assert_eq!(netifas, list_afinet_netifas().unwrap());
+    }
+
+    #[test]
+    #[cfg(netlink_backend)]
+    fn list_loopback_network_interface() {
+        let netifas = list_network_interfaces().unwrap();
+        let loopback = netifas
+            .iter()
+            .find(|ifa| ifa.addr == IpAddr::V4(Ipv4Addr::LOCALHOST))
+            .unwrap();
+
+        assert_eq!(loopback.name, "lo");
+        assert_eq!(loopback.prefix_len, Some(8));
+        assert!(loopback.is_loopback);
+        assert_eq!(loopback.index, Some(1));
+        // the loopback interface reports an all-zeros hardware address
+        assert_eq!(loopback.mac, None);
+        assert_eq!(loopback.mtu, None);
+        assert!(loopback
+            .flags
+            .contains(InterfaceFlags::UP | InterfaceFlags::RUNNING));
+        assert!(!loopback.flags.contains(InterfaceFlags::BROADCAST));
+        assert_eq!(loopback.scope_id, None);
+        assert_eq!(loopback.dest_addr, None);
+        // addresses assigned statically never expire
+        assert_eq!(loopback.valid_lifetime, Some(u32::MAX));
+        assert_eq!(loopback.preferred_lifetime, Some(u32::MAX));
+
+        for ifa in netifas.iter().filter(|ifa| is_link_local(&ifa.addr)) {
+            assert_eq!(ifa.scope_id, ifa.index);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_loopback_addr_with_netmask() {
+        let netifas = list_afinet_netifas_with_netmask().unwrap();
+
+        assert!(netifas.iter().any(|(name, addr, netmask)| name == "lo"
+            && *addr == IpAddr::V4(Ipv4Addr::LOCALHOST)
+            && *netmask == IpAddr::V4(Ipv4Addr::new(255, 0, 0, 0))));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn find_loopback_index() {
+        let netifas = list_afinet_netifas_with_index().unwrap();
+
+        assert!(netifas
+            .iter()
+            .filter(|(name, _, _)| name == "lo")
+            .all(|(_, index, _)| *index == interface_index("lo").unwrap()));
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn loopback_interface_kind() {
+        let ifas = list_afinet_netifas_info().unwrap();
+
+        assert!(ifas
+            .iter()
+            .filter(|ifa| ifa.name == "lo")
+            .all(|ifa| ifa.kind == InterfaceKind::Loopback));
+        assert!(list_network_interfaces()
+            .unwrap()
+            .iter()
+            .filter(|netifa| netifa.is_loopback)
+            .all(|netifa| netifa.kind == InterfaceKind::Loopback));
+        assert_eq!(
+            local_ip_prefer_wired(),
+            local_ip_preferring(InterfaceKind::Ethernet)
+        );
+        // loopback interfaces are never preferred
+        assert_eq!(local_ip_preferring(InterfaceKind::Loopback), local_ip());
     }
 }
@@ -1,23 +1,31 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
 use std::collections::HashMap;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::Path;
 
+use libc::if_nametoindex;
 use neli::attr::Attribute;
 use neli::consts::nl::{NlmF, NlmFFlags};
 use neli::consts::socket::NlFamily;
 use neli::consts::rtnl::{
-    Ifa, IfaFFlags, RtAddrFamily, RtScope, Rtm, RtTable, Rtprot, Rtn, RtmFFlags, RtmF, Rta, Ifla,
-    IffFlags, Arphrd,
+    Ifa, IfaF, IfaFFlags, RtAddrFamily, RtScope, Rtm, RtTable, Rtprot, Rtn, RtmFFlags, RtmF, Rta,
+    Ifla, Iff, IffFlags, Arphrd, RtaType,
 };
 use neli::nl::{NlPayload, Nlmsghdr};
 use neli::rtnl::{Ifaddrmsg, Ifinfomsg, Rtattr, Rtmsg};
 
 use neli::socket::NlSocketHandle;
-use neli::types::RtBuffer;
+use neli::types::{Buffer, RtBuffer};
 use neli::consts::rtnl::RtAddrFamily::{Inet, Inet6};
 use neli::err::NlError::Nlmsgerr;
+use neli::err::{SerError, WrappedError};
 
-use crate::Error;
+use crate::interface::mac_from_bytes;
+use crate::{
+    AddrFamily, DestAddr, Error, InterfaceAddr, InterfaceFlags, InterfaceKind, NetworkInterface,
+};
 
 #[cfg(target_env = "gnu")]
 const RTM_FLAGS_LOOKUP: &[RtmF] = &[RtmF::LookupTable];
@@ -26,12 +34,75 @@ const RTM_FLAGS_LOOKUP: &[RtmF] = &[];
 
 /// Retrieves the local IPv4 address for this system
 pub fn local_ip() -> Result<IpAddr, Error> {
-    local_ip_impl(Inet)
+    NetlinkClient::new()?.local_ip()
 }
 
 /// Retrieves the local IPv6 address for this system
 pub fn local_ipv6() -> Result<IpAddr, Error> {
-    local_ip_impl(Inet6)
+    NetlinkClient::new()?.local_ipv6()
+}
+
+/// Netlink Route client which reuses a single socket across queries, sparing
+/// the cost of connecting a new socket on each call when the system is queried
+/// repeatedly.
+///
+/// A query which fails may leave responses unread on the socket, the socket is
+/// replaced in such case so they are never received by the next query.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::NetlinkClient;
+///
+/// let mut client = NetlinkClient::new().unwrap();
+///
+/// println!("IPv4: {:?}", client.local_ip());
+/// println!("IPv6: {:?}", client.local_ipv6());
+/// ```
+pub struct NetlinkClient {
+    netlink_socket: NlSocketHandle,
+}
+
+impl NetlinkClient {
+    /// Connects a new Netlink socket to the `NlFamily::Route` family
+    pub fn new() -> Result<NetlinkClient, Error> {
+        let netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+
+        Ok(NetlinkClient { netlink_socket })
+    }
+
+    /// Retrieves the local IPv4 address for this system, refer to [`local_ip`]
+    pub fn local_ip(&mut self) -> Result<IpAddr, Error> {
+        self.query(|netlink_socket| local_ip_with_socket(netlink_socket, Inet))
+    }
+
+    /// Retrieves the local IPv6 address for this system, refer to [`local_ipv6`]
+    pub fn local_ipv6(&mut self) -> Result<IpAddr, Error> {
+        self.query(|netlink_socket| local_ip_with_socket(netlink_socket, Inet6))
+    }
+
+    /// Perform a search over the system's network interfaces, refer to
+    /// [`list_afinet_netifas`]
+    pub fn list_afinet_netifas(&mut self) -> Result<Vec<(String, IpAddr)>, Error> {
+        self.query(list_afinet_netifas_with_socket)
+    }
+
+    fn query<T, F>(&mut self, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&mut NlSocketHandle) -> Result<T, Error>,
+    {
+        let result = f(&mut self.netlink_socket);
+
+        if result.is_err() {
+            // the socket is kept if a new one can't be connected, the next
+            // query might fail to parse leftover responses then
+            if let Ok(client) = NetlinkClient::new() {
+                *self = client;
+            }
+        }
+
+        result
+    }
 }
 
 /// Retrieves the local broadcast IPv4 address for this system
@@ -39,9 +110,41 @@ pub fn local_broadcast_ip() -> Result<IpAddr, Error> {
     local_broadcast_impl(Inet)
 }
 
+/// Retrieves the IPv4 address of the default gateway for this system
+///
+/// The default routes of the main table are dumped through `RTM_GETROUTE` and
+/// the gateway is read from the `RTA_GATEWAY` attribute of the one with the
+/// lowest priority (metric).
+pub fn default_gateway() -> Result<IpAddr, Error> {
+    default_gateway_impl(Inet)
+}
+
+/// Retrieves the IPv6 address of the default gateway for this system
+///
+/// The IPv6 default routes of the main table are dumped through `RTM_GETROUTE`
+/// just as [`default_gateway`] does, so the gateway is found even if no IPv4
+/// default route is configured.
+pub fn default_gateway_v6() -> Result<IpAddr, Error> {
+    default_gateway_impl(Inet6)
+}
+
+fn default_gateway_impl(family: RtAddrFamily) -> Result<IpAddr, Error> {
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+
+    default_routes(family, &mut netlink_socket)?
+        .into_iter()
+        .filter_map(|route| route.gateway.map(|gateway| (route.priority, gateway)))
+        .min_by_key(|(priority, _)| *priority)
+        .map(|(_, gateway)| gateway)
+        .ok_or(Error::LocalIpAddressNotFound)
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", fields(family = ?family))
+)]
 fn local_broadcast_impl(family: RtAddrFamily) -> Result<IpAddr, Error> {
-    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])
-        .map_err(|err| Error::StrategyError(err.to_string()))?;
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
 
     let pref_ip = local_ip()?;
 
@@ -62,9 +165,7 @@ fn local_broadcast_impl(family: RtAddrFamily) -> Result<IpAddr, Error> {
         NlPayload::Payload(ifaddrmsg),
     );
 
-    netlink_socket
-        .send(netlink_message)
-        .map_err(|err| Error::StrategyError(err.to_string()))?;
+    netlink_socket.send(netlink_message).map_err(ser_error)?;
 
     let mut broadcast_ip = None;
     for response in netlink_socket.iter(false) {
@@ -154,75 +255,41 @@ fn local_broadcast_impl(family: RtAddrFamily) -> Result<IpAddr, Error> {
     Err(Error::LocalIpAddressNotFound)
 }
 
-fn local_ip_impl(family: RtAddrFamily) -> Result<IpAddr, Error> {
-    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])
-        .map_err(|err| Error::StrategyError(err.to_string()))?;
-
-    match local_ip_impl_route(family, &mut netlink_socket) {
-        Ok(ip_addr) => Ok(ip_addr),
-        Err(Error::LocalIpAddressNotFound) => local_ip_impl_addr(family, &mut netlink_socket),
-        Err(e) => Err(e),
-    }
-}
-
-fn local_ip_impl_route(
-    family: RtAddrFamily,
-    netlink_socket: &mut NlSocketHandle,
-) -> Result<IpAddr, Error> {
-    let route_attr = match family {
-        Inet => {
-            let dstip = Ipv4Addr::new(192, 0, 2, 0); // reserved external IP
-            let raw_dstip = u32::from(dstip).to_be();
-            Rtattr::new(None, Rta::Dst, raw_dstip)
-        }
-        Inet6 => {
-            let dstip = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0); // reserved external IP
-            let raw_dstip = u128::from(dstip).to_be();
-            Rtattr::new(None, Rta::Dst, raw_dstip)
-        }
-        _ => Err(Error::StrategyError(format!(
-            "Invalid address family given: {:#?}",
-            family
-        )))?,
-    };
+/// Retrieves the broadcast IPv4 address of the network interface with the
+/// given name, as held by the `IFA_BROADCAST` attribute of its addresses.
+///
+/// Returns `Error::InterfaceNotFound` if no interface with such name exists, and
+/// `Error::LocalIpAddressNotFound` if none of its IPv4 addresses has a broadcast
+/// address, as is the case for loopback and point-to-point interfaces.
+pub fn broadcast_ip_on(name: &str) -> Result<IpAddr, Error> {
+    let index = interface_index(name)?;
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
 
-    let route_attr = route_attr.map_err(|err| Error::StrategyError(err.to_string()))?;
-    let mut route_payload = RtBuffer::new();
-    route_payload.push(route_attr);
-    let ifroutemsg = Rtmsg {
-        rtm_family: family,
-        rtm_dst_len: 0,
-        rtm_src_len: 0,
-        rtm_tos: 0,
-        rtm_table: RtTable::Unspec,
-        rtm_protocol: Rtprot::Unspec,
-        rtm_scope: RtScope::Universe,
-        rtm_type: Rtn::Unspec,
-        rtm_flags: RtmFFlags::new(RTM_FLAGS_LOOKUP),
-        rtattrs: route_payload,
+    let ifaddrmsg = Ifaddrmsg {
+        ifa_family: Inet,
+        ifa_prefixlen: 0,
+        ifa_flags: IfaFFlags::empty(),
+        ifa_scope: 0,
+        ifa_index: 0,
+        rtattrs: RtBuffer::new(),
     };
     let netlink_message = Nlmsghdr::new(
         None,
-        Rtm::Getroute,
-        NlmFFlags::new(&[NlmF::Request]),
+        Rtm::Getaddr,
+        NlmFFlags::new(&[NlmF::Request, NlmF::Dump]),
         None,
         None,
-        NlPayload::Payload(ifroutemsg),
+        NlPayload::Payload(ifaddrmsg),
     );
 
-    netlink_socket
-        .send(netlink_message)
-        .map_err(|err| Error::StrategyError(err.to_string()))?;
+    netlink_socket.send(netlink_message).map_err(ser_error)?;
+
+    let mut broadcast_ip = None;
 
     for response in netlink_socket.iter(false) {
-        let header: Nlmsghdr<Rtm, Rtmsg> = response.map_err(|err| {
-            if let Nlmsgerr(ref err) = err {
-                if err.error == -libc::ENETUNREACH {
-                    return Error::LocalIpAddressNotFound;
-                }
-            }
-            Error::StrategyError(format!(
-                "An error occurred retrieving Netlink's socket response: {err}",
+        let header: Nlmsghdr<Rtm, Ifaddrmsg> = response.map_err(|_| {
+            Error::StrategyError(String::from(
+                "An error occurred retrieving Netlink's socket response",
             ))
         })?;
 
@@ -230,7 +297,7 @@ fn local_ip_impl_route(
             continue;
         }
 
-        if header.nl_type != Rtm::Newroute {
+        if header.nl_type != Rtm::Newaddr {
             return Err(Error::StrategyError(String::from(
                 "The Netlink header type is not the expected",
             )));
@@ -242,68 +309,68 @@ fn local_ip_impl_route(
             ))
         })?;
 
-        if p.rtm_scope != RtScope::Universe {
+        // the dump is not filtered by the kernel, the whole of it is read
+        // to leave the socket ready for the next request
+        if broadcast_ip.is_some() || p.ifa_index != index as i32 || p.ifa_family != Inet {
             continue;
         }
 
-        if p.rtm_family != family {
-            Err(Error::StrategyError(format!(
-                "Invalid address family in Netlink payload: {:?}",
-                p.rtm_family
-            )))?
-        }
-
         for rtattr in p.rtattrs.iter() {
-            if rtattr.rta_type == Rta::Prefsrc {
-                if p.rtm_family == Inet {
-                    let addr = Ipv4Addr::from(u32::from_be(
-                        rtattr.get_payload_as::<u32>().map_err(|_| {
-                            Error::StrategyError(String::from(
-                                "An error occurred retrieving Netlink's route payload attribute",
-                            ))
-                        })?,
-                    ));
-                    return Ok(IpAddr::V4(addr));
-                } else {
-                    let addr = Ipv6Addr::from(u128::from_be(
-                        rtattr.get_payload_as::<u128>().map_err(|_| {
-                            Error::StrategyError(String::from(
-                                "An error occurred retrieving Netlink's route payload attribute",
-                            ))
-                        })?,
-                    ));
-                    return Ok(IpAddr::V6(addr));
-                }
+            if rtattr.rta_type == Ifa::Broadcast {
+                broadcast_ip = Some(parse_addr_attr(p.ifa_family, rtattr)?);
+                break;
             }
         }
     }
-    Err(Error::LocalIpAddressNotFound)
+
+    broadcast_ip.ok_or(Error::LocalIpAddressNotFound)
 }
 
-fn local_ip_impl_addr(
-    family: RtAddrFamily,
-    netlink_socket: &mut NlSocketHandle,
-) -> Result<IpAddr, Error> {
+/// Retrieves the addresses of the network interface with the given index,
+/// without resolving its name.
+///
+/// The index is set in the `RTM_GETADDR` request so kernels with strict
+/// checking enabled filter the dump, the addresses of other interfaces are
+/// skipped otherwise. An empty `Vec` is returned when no interface with the
+/// given index holds an address.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::addresses_for_index;
+///
+/// for ip_address in addresses_for_index(1).unwrap() {
+///     println!("{:?}", ip_address);
+/// }
+/// ```
+pub fn addresses_for_index(index: u32) -> Result<Vec<IpAddr>, Error> {
+    let index = match i32::try_from(index) {
+        Ok(index) => index,
+        // the kernel doesn't assign indexes out of the range of `int`
+        Err(_) => return Ok(Vec::new()),
+    };
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+
     let ifaddrmsg = Ifaddrmsg {
-        ifa_family: family,
+        ifa_family: RtAddrFamily::Unspecified,
         ifa_prefixlen: 0,
         ifa_flags: IfaFFlags::empty(),
         ifa_scope: 0,
-        ifa_index: 0,
+        ifa_index: index,
         rtattrs: RtBuffer::new(),
     };
     let netlink_message = Nlmsghdr::new(
         None,
         Rtm::Getaddr,
-        NlmFFlags::new(&[NlmF::Request, NlmF::Root]),
+        NlmFFlags::new(&[NlmF::Request, NlmF::Dump]),
         None,
         None,
         NlPayload::Payload(ifaddrmsg),
     );
 
-    netlink_socket
-        .send(netlink_message)
-        .map_err(|err| Error::StrategyError(err.to_string()))?;
+    netlink_socket.send(netlink_message).map_err(ser_error)?;
+
+    let mut ip_addresses = Vec::new();
 
     for response in netlink_socket.iter(false) {
         let header: Nlmsghdr<Rtm, Ifaddrmsg> = response.map_err(|_| {
@@ -328,107 +395,920 @@ fn local_ip_impl_addr(
             ))
         })?;
 
-        if RtScope::from(p.ifa_scope) != RtScope::Universe {
+        if p.ifa_index != index {
             continue;
         }
 
-        if p.ifa_family != family {
-            Err(Error::StrategyError(format!(
-                "Invalid family in Netlink payload: {:?}",
-                p.ifa_family
-            )))?
+        if let Some(addr_attrs) = parse_addr_attrs(p)? {
+            ip_addresses.push(addr_attrs.addr);
         }
+    }
 
-        for rtattr in p.rtattrs.iter() {
-            if rtattr.rta_type == Ifa::Local {
-                if p.ifa_family == Inet {
-                    let addr = Ipv4Addr::from(u32::from_be(
-                        rtattr.get_payload_as::<u32>().map_err(|_| {
-                            Error::StrategyError(String::from(
-                                "An error occurred retrieving Netlink's route payload attribute",
-                            ))
-                        })?,
-                    ));
-                    return Ok(IpAddr::V4(addr));
-                } else {
-                    let addr = Ipv6Addr::from(u128::from_be(
-                        rtattr.get_payload_as::<u128>().map_err(|_| {
-                            Error::StrategyError(String::from(
-                                "An error occurred retrieving Netlink's route payload attribute",
-                            ))
-                        })?,
-                    ));
-                    return Ok(IpAddr::V6(addr));
-                }
-            }
+    Ok(ip_addresses)
+}
+
+/// Retrieves the local IP address of the family, `AF_INET` or `AF_INET6`, whose
+/// best default route has the lowest priority (metric, `RTA_PRIORITY`). IPv6 is
+/// preferred when both have the same priority.
+///
+/// This is a heuristic which may not match the family the system picks for a
+/// connection, as the destination address is chosen among the ones of both
+/// families by the RFC 6724 destination address selection of `getaddrinfo`,
+/// regardless of route metrics. For instance, default routes learned through
+/// router advertisements have a metric of 1024 while IPv4 default routes
+/// configured through DHCP usually have a metric of 100, so the IPv4 address is
+/// returned even if new connections to dual-stack hosts go over IPv6.
+pub fn best_local_ip() -> Result<IpAddr, Error> {
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+
+    let mut best: Option<(u32, IpAddr)> = None;
+
+    for family in [Inet6, Inet] {
+        let priority = match default_routes(family, &mut netlink_socket)?
+            .into_iter()
+            .map(|route| route.priority)
+            .min()
+        {
+            Some(priority) => priority,
+            None => continue,
+        };
+
+        if matches!(best, Some((best_priority, _)) if best_priority <= priority) {
+            continue;
+        }
+
+        match local_ip_with_socket(&mut netlink_socket, family) {
+            Ok(ip_addr) => best = Some((priority, ip_addr)),
+            Err(Error::LocalIpAddressNotFound) => continue,
+            Err(e) => return Err(e),
         }
     }
 
-    Err(Error::LocalIpAddressNotFound)
+    best.map(|(_, ip_addr)| ip_addr)
+        .ok_or(Error::LocalIpAddressNotFound)
 }
 
-/// Perform a search over the system's network interfaces using Netlink Route information,
-/// retrieved network interfaces belonging to both socket address families
-/// `AF_INET` and `AF_INET6` are retrieved along with the interface address name.
+/// Determines if IPv6 is administratively disabled on the network interface
+/// with the given name, as told by the `net.ipv6.conf.<name>.disable_ipv6`
+/// sysctl. IPv6 may be disabled even if the interface holds no IPv6 address.
+///
+/// Returns `Error::InterfaceNotFound` if no interface with such name exists, and
+/// `Error::StrategyError` if the sysctl can't be read, as is the case on
+/// kernels built without IPv6 or booted with `ipv6.disable=1`.
+pub fn ipv6_disabled(name: &str) -> Result<bool, Error> {
+    // the name is checked before being used as a path component
+    interface_index(name)?;
+
+    let sysctl = Path::new("/proc/sys/net/ipv6/conf")
+        .join(name)
+        .join("disable_ipv6");
+    let value = std::fs::read_to_string(&sysctl).map_err(|err| {
+        Error::StrategyError(format!("Failed to read {}: {}", sysctl.display(), err))
+    })?;
+
+    match value.trim() {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        value => Err(Error::StrategyError(format!(
+            "Unexpected value in {}: {}",
+            sysctl.display(),
+            value
+        ))),
+    }
+}
+
+/// Retrieves the local IP address of the given `family` for this system using
+/// an already connected Netlink socket.
+///
+/// The socket must be connected to the `NlFamily::Route` family, and `family`
+/// must be either `RtAddrFamily::Inet` or `RtAddrFamily::Inet6`. This is useful
+/// to reuse a socket across calls or to keep custom socket options, for instance
+/// when the socket was created inside a specific network namespace.
 ///
 /// # Example
 ///
 /// ```
-/// use std::net::IpAddr;
-/// use local_ip_address::list_afinet_netifas;
+/// use neli::consts::rtnl::RtAddrFamily;
+/// use neli::consts::socket::NlFamily;
+/// use neli::socket::NlSocketHandle;
+/// use local_ip_address::local_ip_with_socket;
 ///
-/// let ifas = list_afinet_netifas().unwrap();
+/// let mut socket = NlSocketHandle::connect(NlFamily::Route, None, &[]).unwrap();
 ///
-/// if let Some((_, ipaddr)) = ifas
-/// .iter()
-/// .find(|(name, ipaddr)| *name == "en0" && matches!(ipaddr, IpAddr::V4(_))) {
-///     // This is your local IP address: 192.168.1.111
-///     println!("This is your local IP address: {:?}", ipaddr);
+/// if let Ok(ip) = local_ip_with_socket(&mut socket, RtAddrFamily::Inet) {
+///     println!("This is my local IP address: {:?}", ip);
 /// }
 /// ```
-pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
-    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])
-        .map_err(|err| Error::StrategyError(err.to_string()))?;
-
-    // First get list of interfaces via RTM_GETLINK
-
-    let ifinfomsg = Ifinfomsg::new(
-        RtAddrFamily::Unspecified,
-        Arphrd::from(0),
-        0,
-        IffFlags::empty(),
-        IffFlags::empty(),
-        RtBuffer::new(),
-    );
+pub fn local_ip_with_socket(
+    netlink_socket: &mut NlSocketHandle,
+    family: RtAddrFamily,
+) -> Result<IpAddr, Error> {
+    match local_ip_impl_route(family, netlink_socket) {
+        Ok(ip_addr) => Ok(ip_addr),
+        Err(Error::LocalIpAddressNotFound) => local_ip_impl_addr(family, netlink_socket),
+        Err(e) => Err(e),
+    }
+}
 
-    let netlink_message = Nlmsghdr::new(
-        None,
-        Rtm::Getlink,
-        NlmFFlags::new(&[NlmF::Request, NlmF::Dump]),
-        None,
-        None,
-        NlPayload::Payload(ifinfomsg),
-    );
+/// Retrieves the local IPv4 address for this system along with the name of the
+/// output interface of the route towards it
+pub fn local_ip_detailed() -> Result<(String, IpAddr), Error> {
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+
+    let dstip = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 0)); // reserved external IP
+    let from_route = route_lookup_msg(dstip, &mut netlink_socket).and_then(|route| {
+        let ip_addr = route_addr_attr(&route, Rta::Prefsrc)?;
+        let name = interface_name(route_oif_attr(&route)?)?;
+
+        Ok((name, ip_addr))
+    });
+
+    match from_route {
+        Ok(detailed) => Ok(detailed),
+        Err(Error::LocalIpAddressNotFound) => {
+            let ip_addr = local_ip_impl_addr(Inet, &mut netlink_socket)?;
+
+            list_afinet_netifas_with_socket(&mut netlink_socket)?
+                .into_iter()
+                .find(|(_, ifaddr)| *ifaddr == ip_addr)
+                .map(|(name, _)| (name, ip_addr))
+                .ok_or(Error::LocalIpAddressNotFound)
+        }
+        Err(e) => Err(e),
+    }
+}
 
-    netlink_socket
-        .send(netlink_message)
-        .map_err(|err| Error::StrategyError(err.to_string()))?;
+/// Retrieves the local IP address the system would use as source address when
+/// sending packets to `dest`, honoring its routing table.
+///
+/// The route towards `dest` is looked up through `RTM_GETROUTE` just as
+/// [`local_ip`] does with a reserved external address, and the local IP address
+/// is read from its `RTA_PREFSRC` attribute. This is useful when the default
+/// route points into the reserved documentation ranges (`192.0.2.0/24` or
+/// `2001:db8::/32`), or to find the address used to reach a peer on the LAN.
+///
+/// Returns `Error::LocalIpAddressNotFound` when there is no route to `dest`.
+pub fn local_ip_toward(dest: IpAddr) -> Result<IpAddr, Error> {
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
 
-    let mut if_indexes = HashMap::new();
+    route_lookup(dest, Rta::Prefsrc, &mut netlink_socket)
+}
 
-    for response in netlink_socket.iter(false) {
-        let header: Nlmsghdr<Rtm, Ifinfomsg> = response.map_err(|_| {
-            Error::StrategyError(String::from(
-                "An error occurred retrieving Netlink's socket response",
-            ))
-        })?;
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(netlink_socket), fields(family = ?family))
+)]
+fn local_ip_impl_route(
+    family: RtAddrFamily,
+    netlink_socket: &mut NlSocketHandle,
+) -> Result<IpAddr, Error> {
+    let dstip = match family {
+        Inet => IpAddr::V4(Ipv4Addr::new(192, 0, 2, 0)), // reserved external IP
+        Inet6 => IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0)), // reserved external IP
+        _ => Err(Error::StrategyError(format!(
+            "Invalid address family given: {:#?}",
+            family
+        )))?,
+    };
 
-        if let NlPayload::Empty = header.nl_payload {
-            continue;
+    match route_lookup(dstip, Rta::Prefsrc, netlink_socket) {
+        // the route towards the reserved address may lack a preferred source,
+        // for instance when the only default route is through a ULA prefix,
+        // in which case the default routes are inspected instead
+        Err(Error::LocalIpAddressNotFound) if family == Inet6 => {
+            default_route_prefsrc(family, netlink_socket)
         }
+        result => result,
+    }
+}
 
-        if header.nl_type != Rtm::Newlink {
-            return Err(Error::StrategyError(String::from(
+/// Retrieves the preferred source address of the best default route of the
+/// given `family`, that is the one with the lowest priority among the default
+/// routes providing `RTA_PREFSRC`.
+fn default_route_prefsrc(
+    family: RtAddrFamily,
+    netlink_socket: &mut NlSocketHandle,
+) -> Result<IpAddr, Error> {
+    default_routes(family, netlink_socket)?
+        .into_iter()
+        .filter_map(|route| route.prefsrc.map(|prefsrc| (route.priority, prefsrc)))
+        .min_by_key(|(priority, _)| *priority)
+        .map(|(_, prefsrc)| prefsrc)
+        .ok_or(Error::LocalIpAddressNotFound)
+}
+
+/// Default route of the main table as dumped through `RTM_GETROUTE`
+struct DefaultRoute {
+    /// Priority (metric) of the route, `0` if it lacks `RTA_PRIORITY`
+    priority: u32,
+    /// Preferred source address of the route (`RTA_PREFSRC`)
+    prefsrc: Option<IpAddr>,
+    /// Gateway of the route (`RTA_GATEWAY`)
+    gateway: Option<IpAddr>,
+}
+
+/// Dumps the routes of the given `family` through `RTM_GETROUTE` and retrieves
+/// the default routes of the main table, in the order they are dumped.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(netlink_socket), fields(family = ?family))
+)]
+fn default_routes(
+    family: RtAddrFamily,
+    netlink_socket: &mut NlSocketHandle,
+) -> Result<Vec<DefaultRoute>, Error> {
+    let ifroutemsg = Rtmsg {
+        rtm_family: family,
+        rtm_dst_len: 0,
+        rtm_src_len: 0,
+        rtm_tos: 0,
+        rtm_table: RtTable::Unspec,
+        rtm_protocol: Rtprot::Unspec,
+        rtm_scope: RtScope::Universe,
+        rtm_type: Rtn::Unspec,
+        rtm_flags: RtmFFlags::empty(),
+        rtattrs: RtBuffer::new(),
+    };
+    let netlink_message = Nlmsghdr::new(
+        None,
+        Rtm::Getroute,
+        NlmFFlags::new(&[NlmF::Request, NlmF::Dump]),
+        None,
+        None,
+        NlPayload::Payload(ifroutemsg),
+    );
+
+    netlink_socket.send(netlink_message).map_err(ser_error)?;
+
+    let mut routes = Vec::new();
+
+    for response in netlink_socket.iter(false) {
+        let header: Nlmsghdr<Rtm, Rtmsg> = response.map_err(|_| {
+            Error::StrategyError(String::from(
+                "An error occurred retrieving Netlink's socket response",
+            ))
+        })?;
+
+        if let NlPayload::Empty = header.nl_payload {
+            continue;
+        }
+
+        if header.nl_type != Rtm::Newroute {
+            return Err(Error::StrategyError(String::from(
+                "The Netlink header type is not the expected",
+            )));
+        }
+
+        let p = header.get_payload().map_err(|_| {
+            Error::StrategyError(String::from(
+                "An error occurred getting Netlink's header payload",
+            ))
+        })?;
+
+        // only default routes of the main table are considered
+        if p.rtm_family != family
+            || p.rtm_dst_len != 0
+            || p.rtm_table != RtTable::Main
+            || p.rtm_type != Rtn::Unicast
+        {
+            continue;
+        }
+
+        let prefsrc = optional_route_addr_attr(p, Rta::Prefsrc)?;
+        let gateway = optional_route_addr_attr(p, Rta::Gateway)?;
+
+        // routes without `RTA_PRIORITY` have the highest priority
+        let priority = p
+            .rtattrs
+            .iter()
+            .find(|rtattr| rtattr.rta_type == Rta::Priority)
+            .and_then(|rtattr| rtattr.get_payload_as::<u32>().ok())
+            .unwrap_or(0);
+
+        routes.push(DefaultRoute {
+            priority,
+            prefsrc,
+            gateway,
+        });
+    }
+
+    Ok(routes)
+}
+
+/// Looks up the route the kernel would use to reach `dstip` through `RTM_GETROUTE`
+/// and retrieves the address held by the `attr` attribute of such route.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(netlink_socket))
+)]
+fn route_lookup(
+    dstip: IpAddr,
+    attr: Rta,
+    netlink_socket: &mut NlSocketHandle,
+) -> Result<IpAddr, Error> {
+    let route = route_lookup_msg(dstip, netlink_socket)?;
+
+    route_addr_attr(&route, attr)
+}
+
+/// Retrieves the address held by the `attr` attribute of a route
+fn route_addr_attr(route: &Rtmsg, attr: Rta) -> Result<IpAddr, Error> {
+    for rtattr in route.rtattrs.iter() {
+        if rtattr.rta_type == attr {
+            if route.rtm_family == Inet {
+                let addr = Ipv4Addr::from(u32::from_be(rtattr.get_payload_as::<u32>().map_err(
+                    |_| {
+                        Error::StrategyError(String::from(
+                            "An error occurred retrieving Netlink's route payload attribute",
+                        ))
+                    },
+                )?));
+                return Ok(IpAddr::V4(addr));
+            } else {
+                let addr = Ipv6Addr::from(u128::from_be(rtattr.get_payload_as::<u128>().map_err(
+                    |_| {
+                        Error::StrategyError(String::from(
+                            "An error occurred retrieving Netlink's route payload attribute",
+                        ))
+                    },
+                )?));
+                return Ok(IpAddr::V6(addr));
+            }
+        }
+    }
+
+    Err(Error::LocalIpAddressNotFound)
+}
+
+/// Retrieves the address held by the `attr` attribute of a route, `None` if the
+/// route lacks such attribute
+fn optional_route_addr_attr(route: &Rtmsg, attr: Rta) -> Result<Option<IpAddr>, Error> {
+    match route_addr_attr(route, attr) {
+        Ok(addr) => Ok(Some(addr)),
+        Err(Error::LocalIpAddressNotFound) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Retrieves the index of the output interface of a route
+fn route_oif_attr(route: &Rtmsg) -> Result<u32, Error> {
+    route
+        .rtattrs
+        .iter()
+        .find(|rtattr| rtattr.rta_type == Rta::Oif)
+        .ok_or(Error::LocalIpAddressNotFound)?
+        .get_payload_as::<u32>()
+        .map_err(|_| {
+            Error::StrategyError(String::from(
+                "An error occurred retrieving Netlink's route payload attribute",
+            ))
+        })
+}
+
+/// Looks up the route the kernel would use to reach `dstip` through `RTM_GETROUTE`
+fn route_lookup_msg(dstip: IpAddr, netlink_socket: &mut NlSocketHandle) -> Result<Rtmsg, Error> {
+    let (family, route_attr) = match dstip {
+        IpAddr::V4(dstip) => {
+            let raw_dstip = u32::from(dstip).to_be();
+            (Inet, Rtattr::new(None, Rta::Dst, raw_dstip))
+        }
+        IpAddr::V6(dstip) => {
+            let raw_dstip = u128::from(dstip).to_be();
+            (Inet6, Rtattr::new(None, Rta::Dst, raw_dstip))
+        }
+    };
+
+    let route_attr = route_attr.map_err(|err| Error::StrategyError(err.to_string()))?;
+    let mut route_payload = RtBuffer::new();
+    route_payload.push(route_attr);
+    let ifroutemsg = Rtmsg {
+        rtm_family: family,
+        rtm_dst_len: 0,
+        rtm_src_len: 0,
+        rtm_tos: 0,
+        rtm_table: RtTable::Unspec,
+        rtm_protocol: Rtprot::Unspec,
+        rtm_scope: RtScope::Universe,
+        rtm_type: Rtn::Unspec,
+        rtm_flags: RtmFFlags::new(RTM_FLAGS_LOOKUP),
+        rtattrs: route_payload,
+    };
+    let netlink_message = Nlmsghdr::new(
+        None,
+        Rtm::Getroute,
+        NlmFFlags::new(&[NlmF::Request]),
+        None,
+        None,
+        NlPayload::Payload(ifroutemsg),
+    );
+
+    netlink_socket.send(netlink_message).map_err(ser_error)?;
+
+    for response in netlink_socket.iter(false) {
+        let header: Nlmsghdr<Rtm, Rtmsg> = response.map_err(|err| {
+            if let Nlmsgerr(ref err) = err {
+                if err.error == -libc::ENETUNREACH {
+                    return Error::LocalIpAddressNotFound;
+                }
+            }
+            Error::StrategyError(format!(
+                "An error occurred retrieving Netlink's socket response: {err}",
+            ))
+        })?;
+
+        if let NlPayload::Empty = header.nl_payload {
+            continue;
+        }
+
+        if header.nl_type != Rtm::Newroute {
+            return Err(Error::StrategyError(String::from(
+                "The Netlink header type is not the expected",
+            )));
+        }
+
+        let p = match header.nl_payload {
+            NlPayload::Payload(p) => p,
+            _ => {
+                return Err(Error::StrategyError(String::from(
+                    "An error occurred getting Netlink's header payload",
+                )))
+            }
+        };
+
+        if p.rtm_scope != RtScope::Universe {
+            continue;
+        }
+
+        if p.rtm_family != family {
+            Err(Error::StrategyError(format!(
+                "Invalid address family in Netlink payload: {:?}",
+                p.rtm_family
+            )))?
+        }
+
+        return Ok(p);
+    }
+    Err(Error::LocalIpAddressNotFound)
+}
+
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(netlink_socket), fields(family = ?family))
+)]
+fn local_ip_impl_addr(
+    family: RtAddrFamily,
+    netlink_socket: &mut NlSocketHandle,
+) -> Result<IpAddr, Error> {
+    let ifaddrmsg = Ifaddrmsg {
+        ifa_family: family,
+        ifa_prefixlen: 0,
+        ifa_flags: IfaFFlags::empty(),
+        ifa_scope: 0,
+        ifa_index: 0,
+        rtattrs: RtBuffer::new(),
+    };
+    let netlink_message = Nlmsghdr::new(
+        None,
+        Rtm::Getaddr,
+        NlmFFlags::new(&[NlmF::Request, NlmF::Root]),
+        None,
+        None,
+        NlPayload::Payload(ifaddrmsg),
+    );
+
+    netlink_socket.send(netlink_message).map_err(ser_error)?;
+
+    let mut preferred_ip = None;
+    let mut deprecated_ip = None;
+
+    for response in netlink_socket.iter(false) {
+        let header: Nlmsghdr<Rtm, Ifaddrmsg> = response.map_err(|_| {
+            Error::StrategyError(String::from(
+                "An error occurred retrieving Netlink's socket response",
+            ))
+        })?;
+
+        if let NlPayload::Empty = header.nl_payload {
+            continue;
+        }
+
+        if header.nl_type != Rtm::Newaddr {
+            return Err(Error::StrategyError(String::from(
+                "The Netlink header type is not the expected",
+            )));
+        }
+
+        let p = header.get_payload().map_err(|_| {
+            Error::StrategyError(String::from(
+                "An error occurred getting Netlink's header payload",
+            ))
+        })?;
+
+        // skips link-local and host scoped addresses
+        if RtScope::from(p.ifa_scope) != RtScope::Universe {
+            continue;
+        }
+
+        // prefer the primary address of an interface over its secondaries
+        if p.ifa_flags.contains(&IfaF::Secondary) {
+            continue;
+        }
+
+        // tentative addresses are not usable until duplicate address detection
+        // succeeds, the ones which failed it will never be
+        if p.ifa_flags.contains(&IfaF::Tentative) || p.ifa_flags.contains(&IfaF::Dadfailed) {
+            continue;
+        }
+
+        if p.ifa_family != family {
+            Err(Error::StrategyError(format!(
+                "Invalid family in Netlink payload: {:?}",
+                p.ifa_family
+            )))?
+        }
+
+        let mut local = None;
+        let mut address = None;
+
+        for rtattr in p.rtattrs.iter() {
+            if rtattr.rta_type == Ifa::Local {
+                local = Some(parse_addr_attr(p.ifa_family, rtattr)?);
+            } else if rtattr.rta_type == Ifa::Address {
+                address = Some(parse_addr_attr(p.ifa_family, rtattr)?);
+            }
+        }
+
+        // IPv6 addresses, as the ones configured through SLAAC, only carry
+        // `IFA_ADDRESS`, `IFA_LOCAL` is preferred when both are available
+        if let Some(addr) = local.or(address) {
+            if p.ifa_flags.contains(&IfaF::Deprecated) {
+                deprecated_ip.get_or_insert(addr);
+            } else {
+                preferred_ip.get_or_insert(addr);
+            }
+        }
+    }
+
+    preferred_ip
+        .or(deprecated_ip)
+        .ok_or(Error::LocalIpAddressNotFound)
+}
+
+/// Parses the IP address held by the payload of a Netlink attribute
+fn parse_addr_attr<T: RtaType>(
+    family: RtAddrFamily,
+    rtattr: &Rtattr<T, Buffer>,
+) -> Result<IpAddr, Error> {
+    let map_err = |_| {
+        Error::StrategyError(String::from(
+            "An error occurred retrieving Netlink's route payload attribute",
+        ))
+    };
+
+    if family == Inet6 {
+        let addr = Ipv6Addr::from(u128::from_be(
+            rtattr.get_payload_as::<u128>().map_err(map_err)?,
+        ));
+        Ok(IpAddr::V6(addr))
+    } else {
+        let addr = Ipv4Addr::from(u32::from_be(
+            rtattr.get_payload_as::<u32>().map_err(map_err)?,
+        ));
+        Ok(IpAddr::V4(addr))
+    }
+}
+
+/// Perform a search over the system's network interfaces using Netlink Route information,
+/// retrieved network interfaces belonging to both socket address families
+/// `AF_INET` and `AF_INET6` are retrieved along with the interface address name.
+///
+/// # Example
+///
+/// ```
+/// use std::net::IpAddr;
+/// use local_ip_address::list_afinet_netifas;
+///
+/// let ifas = list_afinet_netifas().unwrap();
+///
+/// if let Some((_, ipaddr)) = ifas
+/// .iter()
+/// .find(|(name, ipaddr)| *name == "en0" && matches!(ipaddr, IpAddr::V4(_))) {
+///     // This is your local IP address: 192.168.1.111
+///     println!("This is your local IP address: {:?}", ipaddr);
+/// }
+/// ```
+pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
+    NetlinkClient::new()?.list_afinet_netifas()
+}
+
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas`] does, yielding the addresses through an iterator.
+///
+/// Netlink responses are received in full before the first address is
+/// yielded, the iterator doesn't spare the allocation on Linux.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::iter_afinet_netifas;
+///
+/// let first = iter_afinet_netifas().unwrap().find(|(_, ip)| ip.is_ipv4());
+///
+/// println!("First IPv4 address: {:?}", first);
+/// ```
+pub fn iter_afinet_netifas() -> Result<impl Iterator<Item = (String, IpAddr)>, Error> {
+    Ok(list_afinet_netifas()?.into_iter())
+}
+
+/// Perform a search over the system's network interfaces using Netlink Route
+/// information, each address belonging to the `AF_INET` or `AF_INET6` family
+/// is retrieved along with its prefix length and the index, MAC address
+/// (`IFLA_ADDRESS`), MTU (`IFLA_MTU`) and flags (`ifi_flags`) of its interface.
+/// The MTU of the loopback interface is not reported. The scope id of
+/// link-local IPv6 addresses is the index of their interface.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_network_interfaces;
+///
+/// for ifa in list_network_interfaces().unwrap() {
+///     println!("{} ({:?}):\t{:?}/{:?}", ifa.name, ifa.index, ifa.addr, ifa.prefix_len);
+/// }
+/// ```
+pub fn list_network_interfaces() -> Result<Vec<NetworkInterface>, Error> {
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+
+    let link_addrs =
+        list_afinet_netifas_info_impl(&mut netlink_socket, RtAddrFamily::Unspecified, None)?;
+
+    Ok(link_addrs
+        .into_iter()
+        .map(|link_addr| NetworkInterface {
+            name: link_addr.ifa.name,
+            addr: link_addr.ifa.addr,
+            prefix_len: Some(link_addr.ifa.prefix_len),
+            is_loopback: link_addr.ifa.is_loopback,
+            index: link_addr.index,
+            mac: link_addr.mac,
+            mtu: link_addr.mtu,
+            flags: link_addr.flags,
+            kind: link_addr.ifa.kind,
+            // the kernel scopes link-local addresses to their interface
+            scope_id: if crate::is_link_local(&link_addr.ifa.addr) {
+                link_addr.index
+            } else {
+                None
+            },
+            dest_addr: link_addr.dest_addr,
+            valid_lifetime: link_addr.lifetimes.map(|(_, valid)| valid),
+            preferred_lifetime: link_addr.lifetimes.map(|(preferred, _)| preferred),
+        })
+        .collect())
+}
+
+/// Perform a search over the system's network interfaces using Netlink Route information
+/// just as [`list_afinet_netifas`] does, each address is paired with a flag which is
+/// `true` when the address is the primary address of the interface.
+///
+/// An address is considered secondary when the kernel sets `IFA_F_SECONDARY` on it, this
+/// happens when an interface holds more than one address on the same subnet.
+pub fn list_afinet_netifas_with_primary() -> Result<Vec<(String, IpAddr, bool)>, Error> {
+    let ifas = list_afinet_netifas_info()?;
+
+    Ok(ifas
+        .into_iter()
+        .map(|ifa| (ifa.name, ifa.addr, ifa.is_primary))
+        .collect())
+}
+
+/// Details on a network interface gathered from the `RTM_GETLINK` dump
+struct LinkInfo {
+    name: String,
+    is_loopback: bool,
+    is_up: bool,
+    is_multicast: bool,
+    kind: InterfaceKind,
+    mac: Option<[u8; 6]>,
+    mtu: Option<u32>,
+    flags: InterfaceFlags,
+}
+
+/// Determines the medium of a network interface from its hardware type.
+///
+/// Wired and wireless interfaces share the `ARPHRD_ETHER` type, wireless ones are
+/// told apart by the `wireless` entry sysfs exposes for them. Ethernet interfaces
+/// which are not backed by a device (bridges, veth pairs, etc.) are virtual.
+fn link_kind(name: &str, ifi_type: Arphrd) -> InterfaceKind {
+    /// Hardware types of IP tunnels, not covered by `Arphrd`
+    const ARPHRD_TUNNELS: &[u16] = &[
+        libc::ARPHRD_TUNNEL,
+        libc::ARPHRD_TUNNEL6,
+        libc::ARPHRD_SIT,
+        libc::ARPHRD_IPGRE,
+    ];
+
+    match ifi_type {
+        Arphrd::Loopback => InterfaceKind::Loopback,
+        Arphrd::Ether => {
+            let sysfs = Path::new("/sys/class/net").join(name);
+
+            if sysfs.join("wireless").exists() {
+                InterfaceKind::Wireless
+            } else if sysfs.join("device").exists() {
+                InterfaceKind::Ethernet
+            } else {
+                InterfaceKind::Other
+            }
+        }
+        // TUN devices, as used by WireGuard or OpenVPN, have no hardware type
+        Arphrd::None => InterfaceKind::Tunnel,
+        Arphrd::UnrecognizedConst(ifi_type) if ARPHRD_TUNNELS.contains(&ifi_type) => {
+            InterfaceKind::Tunnel
+        }
+        _ => InterfaceKind::Other,
+    }
+}
+
+/// Perform a search over the system's network interfaces using Netlink Route information,
+/// retrieved network interfaces belonging to both socket address families
+/// `AF_INET` and `AF_INET6` are retrieved along with details on the interface
+/// holding them.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_afinet_netifas_info;
+///
+/// let ifas = list_afinet_netifas_info().unwrap();
+///
+/// for ifa in ifas.iter().filter(|ifa| !ifa.is_loopback) {
+///     println!("{}:\t{:?}", ifa.name, ifa.addr);
+/// }
+/// ```
+pub fn list_afinet_netifas_info() -> Result<Vec<InterfaceAddr>, Error> {
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+
+    list_afinet_netifas_info_with_socket(&mut netlink_socket, RtAddrFamily::Unspecified)
+}
+
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas`] does, only retrieving addresses of the given `family`.
+///
+/// The family is set on the `RTM_GETADDR` request, so addresses of other
+/// families are filtered out by the kernel rather than being enumerated.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::{list_afinet_netifas_family, AddrFamily};
+///
+/// for (name, ip) in list_afinet_netifas_family(AddrFamily::Inet).unwrap() {
+///     println!("{}:\t{:?}", name, ip);
+/// }
+/// ```
+pub fn list_afinet_netifas_family(family: AddrFamily) -> Result<Vec<(String, IpAddr)>, Error> {
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+
+    let family = match family {
+        AddrFamily::Inet => Inet,
+        AddrFamily::Inet6 => Inet6,
+        AddrFamily::Unspec => RtAddrFamily::Unspecified,
+    };
+    let ifas = list_afinet_netifas_info_with_socket(&mut netlink_socket, family)?;
+
+    Ok(ifas.into_iter().map(|ifa| (ifa.name, ifa.addr)).collect())
+}
+
+/// Retrieves the IPv6 addresses of the system's network interfaces along with
+/// the flow information (traffic class and flow label) of their socket address.
+///
+/// Netlink doesn't attach flow information to interface addresses, the flow
+/// information is always zero on Linux.
+pub fn list_afinet_netifas_v6_flowinfo() -> Result<Vec<(String, Ipv6Addr, u32)>, Error> {
+    let ifas = list_afinet_netifas_family(AddrFamily::Inet6)?;
+
+    Ok(ifas
+        .into_iter()
+        .filter_map(|(name, addr)| match addr {
+            IpAddr::V6(addr) => Some((name, addr, 0)),
+            IpAddr::V4(_) => None,
+        })
+        .collect())
+}
+
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas`] does, skipping malformed entries instead of failing.
+///
+/// Links and addresses whose Netlink attributes can't be parsed are skipped,
+/// their count is returned along with the entries which were retrieved. Errors
+/// on the Netlink socket itself still fail the search.
+pub fn list_afinet_netifas_lenient() -> Result<(Vec<(String, IpAddr)>, usize), Error> {
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+
+    let mut skipped = 0;
+    let ifas = list_afinet_netifas_info_impl(
+        &mut netlink_socket,
+        RtAddrFamily::Unspecified,
+        Some(&mut skipped),
+    )?;
+
+    Ok((
+        crate::dedup_netifas(
+            ifas.into_iter()
+                .map(|link_addr| (link_addr.ifa.name, link_addr.ifa.addr)),
+        )
+        .collect(),
+        skipped,
+    ))
+}
+
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas`] does, using an already connected Netlink socket.
+///
+/// The socket must be connected to the `NlFamily::Route` family, refer to
+/// [`local_ip_with_socket`] for details.
+pub fn list_afinet_netifas_with_socket(
+    netlink_socket: &mut NlSocketHandle,
+) -> Result<Vec<(String, IpAddr)>, Error> {
+    let ifas = list_afinet_netifas_info_with_socket(netlink_socket, RtAddrFamily::Unspecified)?;
+
+    // bonding and bridge setups may report a same address more than once
+    Ok(crate::dedup_netifas(ifas.into_iter().map(|ifa| (ifa.name, ifa.addr))).collect())
+}
+
+fn list_afinet_netifas_info_with_socket(
+    netlink_socket: &mut NlSocketHandle,
+    family: RtAddrFamily,
+) -> Result<Vec<InterfaceAddr>, Error> {
+    let link_addrs = list_afinet_netifas_info_impl(netlink_socket, family, None)?;
+
+    Ok(link_addrs
+        .into_iter()
+        .map(|link_addr| link_addr.ifa)
+        .collect())
+}
+
+/// Interface address along with the details of its link which are only
+/// exposed through [`NetworkInterface`]
+struct LinkAddr {
+    ifa: InterfaceAddr,
+    index: Option<u32>,
+    mac: Option<[u8; 6]>,
+    mtu: Option<u32>,
+    flags: InterfaceFlags,
+    dest_addr: Option<DestAddr>,
+    lifetimes: Option<(u32, u32)>,
+}
+
+/// Retrieves the interface addresses of the given `family`, malformed links and
+/// addresses are counted in `skipped` when given instead of failing the search.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(netlink_socket, skipped), fields(interfaces = tracing::field::Empty))
+)]
+fn list_afinet_netifas_info_impl(
+    netlink_socket: &mut NlSocketHandle,
+    family: RtAddrFamily,
+    mut skipped: Option<&mut usize>,
+) -> Result<Vec<LinkAddr>, Error> {
+    // First get list of interfaces via RTM_GETLINK
+
+    let ifinfomsg = Ifinfomsg::new(
+        RtAddrFamily::Unspecified,
+        Arphrd::from(0),
+        0,
+        IffFlags::empty(),
+        IffFlags::empty(),
+        RtBuffer::new(),
+    );
+
+    let netlink_message = Nlmsghdr::new(
+        None,
+        Rtm::Getlink,
+        NlmFFlags::new(&[NlmF::Request, NlmF::Dump]),
+        None,
+        None,
+        NlPayload::Payload(ifinfomsg),
+    );
+
+    netlink_socket.send(netlink_message).map_err(ser_error)?;
+
+    let mut links = HashMap::new();
+
+    for response in netlink_socket.iter(false) {
+        let header: Nlmsghdr<Rtm, Ifinfomsg> = response.map_err(|_| {
+            Error::StrategyError(String::from(
+                "An error occurred retrieving Netlink's socket response",
+            ))
+        })?;
+
+        if let NlPayload::Empty = header.nl_payload {
+            continue;
+        }
+
+        if header.nl_type != Rtm::Newlink {
+            return Err(Error::StrategyError(String::from(
                 "The Netlink header type is not the expected",
             )));
         }
@@ -439,19 +1319,19 @@ pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
             ))
         })?;
 
-        for rtattr in p.rtattrs.iter() {
-            if rtattr.rta_type == Ifla::Ifname {
-                let ifname = parse_ifname(rtattr.payload().as_ref())?;
-                if_indexes.insert(p.ifi_index, ifname);
-                break;
+        match parse_link(p) {
+            Ok(Some(link)) => {
+                links.insert(p.ifi_index, link);
             }
+            Ok(None) => {}
+            Err(e) => skip_or_fail(&mut skipped, e)?,
         }
     }
 
     // Secondly get addresses of interfaces via RTM_GETADDR
 
     let ifaddrmsg = Ifaddrmsg {
-        ifa_family: RtAddrFamily::Unspecified,
+        ifa_family: family,
         ifa_prefixlen: 0,
         ifa_flags: IfaFFlags::empty(),
         ifa_scope: 0,
@@ -467,9 +1347,7 @@ pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
         NlPayload::Payload(ifaddrmsg),
     );
 
-    netlink_socket
-        .send(netlink_message)
-        .map_err(|err| Error::StrategyError(err.to_string()))?;
+    netlink_socket.send(netlink_message).map_err(ser_error)?;
 
     let mut interfaces = Vec::new();
 
@@ -496,77 +1374,397 @@ pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
             ))
         })?;
 
-        if p.ifa_family != Inet6 && p.ifa_family != Inet {
-            Err(Error::StrategyError(format!(
-                "Netlink payload has unsupported family: {:?}",
-                p.ifa_family
-            )))?
+        match parse_interface_addr(p, &links) {
+            Ok(Some(interface)) => interfaces.push(interface),
+            Ok(None) => {}
+            Err(e) => skip_or_fail(&mut skipped, e)?,
         }
+    }
 
-        let mut ipaddr = None;
-        let mut label = None;
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("interfaces", interfaces.len());
 
-        for rtattr in p.rtattrs.iter() {
-            if rtattr.rta_type == Ifa::Label {
-                let ifname = parse_ifname(rtattr.payload().as_ref())?;
-                label = Some(ifname);
-            } else if rtattr.rta_type == Ifa::Address {
-                if ipaddr.is_some() {
-                    // do not override IFA_LOCAL
-                    continue;
-                }
-                if p.ifa_family == Inet6 {
-                    let rtaddr = Ipv6Addr::from(u128::from_be(
-                        rtattr.get_payload_as::<u128>().map_err(|_| {
-                            Error::StrategyError(String::from(
-                                "An error occurred retrieving Netlink's route payload attribute",
-                            ))
-                        })?,
-                    ));
-                    ipaddr = Some(IpAddr::V6(rtaddr));
-                } else {
-                    let rtaddr = Ipv4Addr::from(u32::from_be(
-                        rtattr.get_payload_as::<u32>().map_err(|_| {
-                            Error::StrategyError(String::from(
-                                "An error occurred retrieving Netlink's route payload attribute",
-                            ))
-                        })?,
-                    ));
-                    ipaddr = Some(IpAddr::V4(rtaddr));
-                }
-            } else if rtattr.rta_type == Ifa::Local {
-                if p.ifa_family == Inet6 {
-                    let rtlocal = Ipv6Addr::from(u128::from_be(
-                        rtattr.get_payload_as::<u128>().map_err(|_| {
-                            Error::StrategyError(String::from(
-                                "An error occurred retrieving Netlink's route payload attribute",
-                            ))
-                        })?,
-                    ));
-                    ipaddr = Some(IpAddr::V6(rtlocal));
-                } else {
-                    let rtlocal = Ipv4Addr::from(u32::from_be(
-                        rtattr.get_payload_as::<u32>().map_err(|_| {
-                            Error::StrategyError(String::from(
-                                "An error occurred retrieving Netlink's route payload attribute",
-                            ))
-                        })?,
-                    ));
-                    ipaddr = Some(IpAddr::V4(rtlocal));
-                }
-            }
+    Ok(interfaces)
+}
+
+/// Counts a malformed entry as skipped when `skipped` is given, otherwise
+/// fails with its error
+fn skip_or_fail(skipped: &mut Option<&mut usize>, error: Error) -> Result<(), Error> {
+    match skipped {
+        Some(skipped) => {
+            **skipped += 1;
+            Ok(())
+        }
+        None => Err(error),
+    }
+}
+
+/// Retrieves the details of a link from a `RTM_NEWLINK` message, links without
+/// a name are skipped
+fn parse_link(p: &Ifinfomsg) -> Result<Option<LinkInfo>, Error> {
+    let rtattr = match p
+        .rtattrs
+        .iter()
+        .find(|rtattr| rtattr.rta_type == Ifla::Ifname)
+    {
+        Some(rtattr) => rtattr,
+        None => return Ok(None),
+    };
+    let ifname = parse_ifname(rtattr.payload().as_ref())?;
+    let mac = p
+        .rtattrs
+        .iter()
+        .find(|rtattr| rtattr.rta_type == Ifla::Address)
+        .and_then(|rtattr| mac_from_bytes(rtattr.payload().as_ref()));
+    let mtu = p
+        .rtattrs
+        .iter()
+        .find(|rtattr| rtattr.rta_type == Ifla::Mtu)
+        .and_then(|rtattr| rtattr.get_payload_as::<u32>().ok())
+        .filter(|mtu| *mtu > 0 && !p.ifi_flags.contains(&Iff::Loopback));
+    let mut flags = InterfaceFlags::empty();
+    flags.set(InterfaceFlags::UP, p.ifi_flags.contains(&Iff::Up));
+    flags.set(InterfaceFlags::RUNNING, p.ifi_flags.contains(&Iff::Running));
+    flags.set(
+        InterfaceFlags::BROADCAST,
+        p.ifi_flags.contains(&Iff::Broadcast),
+    );
+    flags.set(
+        InterfaceFlags::MULTICAST,
+        p.ifi_flags.contains(&Iff::Multicast),
+    );
+    flags.set(
+        InterfaceFlags::POINTOPOINT,
+        p.ifi_flags.contains(&Iff::Pointopoint),
+    );
+
+    Ok(Some(LinkInfo {
+        is_loopback: p.ifi_flags.contains(&Iff::Loopback),
+        is_up: p.ifi_flags.contains(&Iff::Up) && p.ifi_flags.contains(&Iff::Running),
+        is_multicast: p.ifi_flags.contains(&Iff::Multicast),
+        kind: link_kind(&ifname, p.ifi_type),
+        name: ifname,
+        mac,
+        mtu,
+        flags,
+    }))
+}
+
+/// Retrieves an interface address from a `RTM_NEWADDR` message, addresses of
+/// unknown links without a label are skipped
+fn parse_interface_addr(
+    p: &Ifaddrmsg,
+    links: &HashMap<i32, LinkInfo>,
+) -> Result<Option<LinkAddr>, Error> {
+    let AddrAttrs {
+        addr: ipaddr,
+        dest_addr,
+        label,
+        lifetimes,
+    } = match parse_addr_attrs(p)? {
+        Some(addr_attrs) => addr_attrs,
+        None => return Ok(None),
+    };
+    let link = links.get(&p.ifa_index);
+    let name = match (label, link) {
+        (Some(ifname), _) => ifname,
+        (None, Some(link)) => link.name.clone(),
+        (None, None) => return Ok(None),
+    };
+
+    let ifa = InterfaceAddr {
+        name,
+        addr: ipaddr,
+        is_loopback: link.map(|link| link.is_loopback).unwrap_or(false),
+        is_primary: !p.ifa_flags.contains(&IfaF::Secondary),
+        is_up: link.map(|link| link.is_up).unwrap_or(false),
+        is_multicast: link.map(|link| link.is_multicast).unwrap_or(false),
+        kind: link.map(|link| link.kind).unwrap_or(InterfaceKind::Unknown),
+        prefix_len: p.ifa_prefixlen,
+    };
+
+    Ok(Some(LinkAddr {
+        ifa,
+        index: u32::try_from(p.ifa_index).ok(),
+        mac: link.and_then(|link| link.mac),
+        mtu: link.and_then(|link| link.mtu),
+        flags: link.map(|link| link.flags).unwrap_or_default(),
+        dest_addr,
+        lifetimes,
+    }))
+}
+
+/// Address attributes of a `RTM_NEWADDR` message
+struct AddrAttrs {
+    addr: IpAddr,
+    dest_addr: Option<DestAddr>,
+    label: Option<String>,
+    /// Preferred and valid lifetimes held by `IFA_CACHEINFO`
+    lifetimes: Option<(u32, u32)>,
+}
+
+/// Retrieves the address, destination address, label and lifetimes of a
+/// `RTM_NEWADDR` message, messages without an address are skipped
+fn parse_addr_attrs(p: &Ifaddrmsg) -> Result<Option<AddrAttrs>, Error> {
+    if p.ifa_family != Inet6 && p.ifa_family != Inet {
+        Err(Error::StrategyError(format!(
+            "Netlink payload has unsupported family: {:?}",
+            p.ifa_family
+        )))?
+    }
+
+    let mut address = None;
+    let mut local = None;
+    let mut broadcast = None;
+    let mut label = None;
+    let mut lifetimes = None;
+
+    for rtattr in p.rtattrs.iter() {
+        if rtattr.rta_type == Ifa::Label {
+            let ifname = parse_ifname(rtattr.payload().as_ref())?;
+            label = Some(ifname);
+        } else if rtattr.rta_type == Ifa::Address {
+            address = Some(parse_addr_attr(p.ifa_family, rtattr)?);
+        } else if rtattr.rta_type == Ifa::Local {
+            local = Some(parse_addr_attr(p.ifa_family, rtattr)?);
+        } else if rtattr.rta_type == Ifa::Broadcast {
+            broadcast = Some(parse_addr_attr(p.ifa_family, rtattr)?);
+        } else if rtattr.rta_type == Ifa::Cacheinfo {
+            lifetimes = Some(parse_cacheinfo(rtattr.payload().as_ref())?);
+        }
+    }
+
+    // IFA_LOCAL holds the address when given, IFA_ADDRESS then holds the
+    // address of the peer on point-to-point interfaces
+    let (ipaddr, dest_addr) = match (local, address) {
+        (Some(local), Some(address)) if local != address => {
+            (local, Some(DestAddr::PointToPoint(address)))
+        }
+        (Some(ipaddr), _) | (None, Some(ipaddr)) => (ipaddr, broadcast.map(DestAddr::Broadcast)),
+        (None, None) => return Ok(None),
+    };
+
+    Ok(Some(AddrAttrs {
+        addr: ipaddr,
+        dest_addr,
+        label,
+        lifetimes,
+    }))
+}
+
+/// Parses the preferred and valid lifetimes, the leading fields of the
+/// `ifa_cacheinfo` struct held by the `IFA_CACHEINFO` attribute
+fn parse_cacheinfo(bytes: &[u8]) -> Result<(u32, u32), Error> {
+    match bytes {
+        [p0, p1, p2, p3, v0, v1, v2, v3, ..] => Ok((
+            u32::from_ne_bytes([*p0, *p1, *p2, *p3]),
+            u32::from_ne_bytes([*v0, *v1, *v2, *v3]),
+        )),
+        _ => Err(Error::StrategyError(String::from(
+            "The Netlink IFA_CACHEINFO attribute is too short",
+        ))),
+    }
+}
+
+/// Counts the addresses of the system's non-loopback network interfaces
+/// belonging to the `AF_INET` and `AF_INET6` families respectively.
+///
+/// Only the RTM_GETADDR dump is performed, interface names are never
+/// retrieved. Host scoped addresses are the ones assigned to the loopback
+/// interface and are not counted.
+pub fn address_family_counts() -> Result<(usize, usize), Error> {
+    let mut netlink_socket = NlSocketHandle::connect(NlFamily::Route, None, &[])?;
+
+    let ifaddrmsg = Ifaddrmsg {
+        ifa_family: RtAddrFamily::Unspecified,
+        ifa_prefixlen: 0,
+        ifa_flags: IfaFFlags::empty(),
+        ifa_scope: 0,
+        ifa_index: 0,
+        rtattrs: RtBuffer::new(),
+    };
+    let netlink_message = Nlmsghdr::new(
+        None,
+        Rtm::Getaddr,
+        NlmFFlags::new(&[NlmF::Request, NlmF::Dump]),
+        None,
+        None,
+        NlPayload::Payload(ifaddrmsg),
+    );
+
+    netlink_socket.send(netlink_message).map_err(ser_error)?;
+
+    let mut counts = (0, 0);
+
+    for response in netlink_socket.iter(false) {
+        let header: Nlmsghdr<Rtm, Ifaddrmsg> = response.map_err(|err| {
+            Error::StrategyError(format!(
+                "An error occurred retrieving Netlink's socket response: {err}"
+            ))
+        })?;
+
+        if let NlPayload::Empty = header.nl_payload {
+            continue;
+        }
+
+        if header.nl_type != Rtm::Newaddr {
+            return Err(Error::StrategyError(String::from(
+                "The Netlink header type is not the expected",
+            )));
+        }
+
+        let p = header.get_payload().map_err(|_| {
+            Error::StrategyError(String::from(
+                "An error occurred getting Netlink's header payload",
+            ))
+        })?;
+
+        if RtScope::from(p.ifa_scope) == RtScope::Host {
+            continue;
+        }
+
+        match p.ifa_family {
+            Inet => counts.0 += 1,
+            Inet6 => counts.1 += 1,
+            _ => {}
         }
+    }
 
-        if let Some(ipaddr) = ipaddr {
-            if let Some(ifname) = label {
-                interfaces.push((ifname, ipaddr));
-            } else if let Some(ifname) = if_indexes.get(&p.ifa_index) {
-                interfaces.push((ifname.clone(), ipaddr));
+    Ok(counts)
+}
+
+/// Performs the `RTM_GETADDR` dump used to list network interfaces and returns
+/// the raw bytes of the kernel's response, every Netlink message up to and
+/// including the `NLMSG_DONE` message, without parsing them.
+///
+/// This is meant for diagnostics, the bytes can be attached to bug reports when
+/// [`list_afinet_netifas`] returns something unexpected.
+///
+/// Returns `Error::StrategyError` when the kernel answers the dump with an
+/// `NLMSG_ERROR` message.
+#[cfg(feature = "debug-netlink")]
+pub fn dump_netlink_addr_response() -> Result<Vec<u8>, Error> {
+    use std::io::Cursor;
+
+    use neli::consts::MAX_NL_LENGTH;
+    use neli::socket::NlSocket;
+    use neli::ToBytes;
+
+    /// Size of `struct nlmsghdr`
+    const NLMSG_HDRLEN: usize = 16;
+
+    let netlink_socket = NlSocket::connect(NlFamily::Route, None, &[])?;
+
+    let ifaddrmsg = Ifaddrmsg {
+        ifa_family: RtAddrFamily::Unspecified,
+        ifa_prefixlen: 0,
+        ifa_flags: IfaFFlags::empty(),
+        ifa_scope: 0,
+        ifa_index: 0,
+        rtattrs: RtBuffer::new(),
+    };
+    let netlink_message = Nlmsghdr::new(
+        None,
+        Rtm::Getaddr,
+        NlmFFlags::new(&[NlmF::Request, NlmF::Dump]),
+        None,
+        None,
+        NlPayload::Payload(ifaddrmsg),
+    );
+
+    let mut request = Cursor::new(Vec::new());
+    netlink_message
+        .to_bytes(&mut request)
+        .map_err(|err| Error::StrategyError(err.to_string()))?;
+    netlink_socket.send(request.get_ref(), 0)?;
+
+    let mut response = Vec::new();
+    let mut buffer = vec![0; MAX_NL_LENGTH];
+
+    loop {
+        let len = netlink_socket.recv(&mut buffer, 0)?;
+        let datagram = &buffer[..len];
+
+        response.extend_from_slice(datagram);
+
+        // Walk the headers of the messages in the datagram looking for the end
+        // of the dump, only the length and type of each message are read
+        let mut offset = 0;
+
+        while offset + NLMSG_HDRLEN <= datagram.len() {
+            let header = &datagram[offset..offset + NLMSG_HDRLEN];
+            let nlmsg_len = u32::from_ne_bytes([header[0], header[1], header[2], header[3]]);
+            let nlmsg_type = u16::from_ne_bytes([header[4], header[5]]);
+
+            if nlmsg_type == libc::NLMSG_DONE as u16 {
+                return Ok(response);
             }
+
+            if nlmsg_type == libc::NLMSG_ERROR as u16 {
+                // `struct nlmsgerr` starts with the negated `errno`
+                let error = datagram
+                    .get(offset + NLMSG_HDRLEN..offset + NLMSG_HDRLEN + 4)
+                    .map(|error| i32::from_ne_bytes([error[0], error[1], error[2], error[3]]));
+
+                return Err(Error::StrategyError(format!(
+                    "Netlink dump failed with error {:?}",
+                    error
+                )));
+            }
+
+            if (nlmsg_len as usize) < NLMSG_HDRLEN {
+                return Err(Error::StrategyError(String::from(
+                    "Netlink message length is shorter than its header",
+                )));
+            }
+
+            // Messages are aligned to 4 bytes
+            offset += (nlmsg_len as usize + 3) & !3;
+        }
+
+        if len == 0 {
+            return Err(Error::StrategyError(String::from(
+                "Netlink socket closed before the end of the dump",
+            )));
         }
     }
+}
 
-    Ok(interfaces)
+/// Converts an error sending a Netlink message, keeping the underlying I/O
+/// error when the socket failed
+fn ser_error(err: SerError) -> Error {
+    match err {
+        SerError::Wrapped(WrappedError::IOError(err)) => Error::Io(err),
+        err => Error::StrategyError(err.to_string()),
+    }
+}
+
+/// Retrieves the name of the network interface with the given index using
+/// `if_indextoname`
+fn interface_name(index: u32) -> Result<String, Error> {
+    let mut ifname = [0 as libc::c_char; libc::IF_NAMESIZE];
+    let ptr = unsafe { libc::if_indextoname(index, ifname.as_mut_ptr()) };
+
+    if ptr.is_null() {
+        return Err(Error::InterfaceNotFound(index.to_string()));
+    }
+
+    let ifname = unsafe { CStr::from_ptr(ifname.as_ptr()) };
+
+    Ok(ifname.to_string_lossy().into_owned())
+}
+
+/// Retrieves the index of the network interface with the given name using
+/// `if_nametoindex`
+pub(crate) fn interface_index(name: &str) -> Result<u32, Error> {
+    let c_name = CString::new(name).map_err(|_| Error::InterfaceNotFound(String::from(name)))?;
+    let index = unsafe { if_nametoindex(c_name.as_ptr()) };
+
+    if index == 0 {
+        return Err(Error::InterfaceNotFound(String::from(name)));
+    }
+
+    Ok(index)
 }
 
 /// Parse network interface name of slice type to string type.
@@ -589,8 +1787,42 @@ fn parse_ifname(bytes: &[u8]) -> Result<String, Error> {
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
-    use crate::linux::parse_ifname;
+    use crate::linux::{parse_cacheinfo, parse_ifname};
+
+    #[test]
+    #[cfg(feature = "debug-netlink")]
+    fn dump_netlink_addr_response_ends_with_done() {
+        let response = crate::linux::dump_netlink_addr_response().unwrap();
+        let mut offset = 0;
+        let mut last_type = None;
+
+        // Walk the headers of the messages just as the dump does
+        while offset + 16 <= response.len() {
+            let header = &response[offset..offset + 16];
+            let nlmsg_len = u32::from_ne_bytes([header[0], header[1], header[2], header[3]]);
+
+            last_type = Some(u16::from_ne_bytes([header[4], header[5]]));
+            assert!(nlmsg_len >= 16);
+            offset += (nlmsg_len as usize + 3) & !3;
+        }
+
+        assert_eq!(offset, response.len());
+        assert_eq!(last_type, Some(libc::NLMSG_DONE as u16));
+    }
+
+    #[test]
+    fn parse_cacheinfo_lifetimes() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&600u32.to_ne_bytes());
+        bytes.extend_from_slice(&3600u32.to_ne_bytes());
+        // cstamp and tstamp
+        bytes.extend_from_slice(&[0; 8]);
+
+        assert_eq!(parse_cacheinfo(&bytes).unwrap(), (600, 3600));
+        assert!(parse_cacheinfo(&bytes[..4]).is_err());
+    }
 
     #[test]
     fn parse_ifname_without_nul() {
@@ -0,0 +1,84 @@
+//! Async counterparts of the blocking functions of this crate, running them on
+//! Tokio's blocking thread pool.
+
+use std::net::IpAddr;
+
+use crate::Error;
+
+/// Retrieves the local IP address just as [`local_ip`](crate::local_ip) does,
+/// without blocking the async runtime.
+///
+/// The lookup is run through `tokio::task::spawn_blocking`, thus this function
+/// must be called from within a Tokio runtime.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() {
+/// use local_ip_address::local_ip_async;
+///
+/// let my_local_ip = local_ip_async().await.unwrap();
+///
+/// println!("This is my local IP address: {:?}", my_local_ip);
+/// # }
+/// ```
+pub async fn local_ip_async() -> Result<IpAddr, Error> {
+    spawn_blocking(crate::local_ip).await
+}
+
+/// Retrieves the addresses of the system's network interfaces just as
+/// [`list_afinet_netifas`](crate::list_afinet_netifas) does, without blocking
+/// the async runtime.
+///
+/// The enumeration is run through `tokio::task::spawn_blocking`, thus this
+/// function must be called from within a Tokio runtime.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() {
+/// use local_ip_address::list_afinet_netifas_async;
+///
+/// let network_interfaces = list_afinet_netifas_async().await.unwrap();
+///
+/// for (name, ip) in network_interfaces.iter() {
+///     println!("{}:\t{:?}", name, ip);
+/// }
+/// # }
+/// ```
+pub async fn list_afinet_netifas_async() -> Result<Vec<(String, IpAddr)>, Error> {
+    spawn_blocking(crate::list_afinet_netifas).await
+}
+
+async fn spawn_blocking<T, F>(f: F) -> Result<T, Error>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, Error> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await.map_err(|_| {
+        Error::StrategyError(String::from(
+            "The blocking task retrieving network interfaces did not complete",
+        ))
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{list_afinet_netifas_async, local_ip_async};
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn async_functions_mirror_sync_functions() {
+        assert_eq!(block_on(local_ip_async()), crate::local_ip());
+        assert_eq!(
+            block_on(list_afinet_netifas_async()),
+            crate::list_afinet_netifas()
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! Retrieval of the DNS servers configured in `/etc/resolv.conf`.
+
+use std::io::ErrorKind;
+use std::net::IpAddr;
+
+use crate::Error;
+
+/// Path of the resolver configuration file
+const RESOLV_CONF_PATH: &str = "/etc/resolv.conf";
+
+/// Retrieves the addresses of the DNS servers listed in `/etc/resolv.conf`.
+///
+/// Returns `Error::PlatformNotSupported` when the file doesn't exist, as is the
+/// case on Android.
+pub(crate) fn dns_server_addresses() -> Result<Vec<IpAddr>, Error> {
+    match std::fs::read_to_string(RESOLV_CONF_PATH) {
+        Ok(resolv_conf) => Ok(parse_nameservers(&resolv_conf)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Err(Error::PlatformNotSupported(
+            std::env::consts::OS.to_string(),
+        )),
+        Err(err) => Err(Error::Io(err)),
+    }
+}
+
+/// Parses the addresses of the `nameserver` entries of a `resolv.conf` file.
+///
+/// Comments starting with `#` or `;` are ignored, and the zone index some
+/// systems append to link-local IPv6 addresses (e.g. `fe80::1%eth0`) is
+/// discarded. Duplicated or malformed entries are skipped.
+fn parse_nameservers(resolv_conf: &str) -> Vec<IpAddr> {
+    let mut nameservers: Vec<IpAddr> = Vec::new();
+
+    for line in resolv_conf.lines() {
+        let line = match line.find(['#', ';']) {
+            Some(comment_start) => &line[..comment_start],
+            None => line,
+        };
+        let mut fields = line.split_whitespace();
+
+        if fields.next() != Some("nameserver") {
+            continue;
+        }
+
+        let addr = match fields.next() {
+            Some(addr) => addr.split('%').next().unwrap_or(addr),
+            None => continue,
+        };
+
+        if let Ok(addr) = addr.parse::<IpAddr>() {
+            if !nameservers.contains(&addr) {
+                nameservers.push(addr);
+            }
+        }
+    }
+
+    nameservers
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::parse_nameservers;
+
+    fn ip(addr: &str) -> IpAddr {
+        addr.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_nameserver_entries() {
+        let resolv_conf = "\
+# Generated by NetworkManager
+search example.com
+nameserver 192.168.1.1
+nameserver   2001:db8::53 ; secondary
+nameserver fe80::1%eth0
+nameserver 192.168.1.1
+;nameserver 10.0.0.1
+nameserver not-an-address
+nameserver
+options edns0
+";
+
+        assert_eq!(
+            parse_nameservers(resolv_conf),
+            vec![ip("192.168.1.1"), ip("2001:db8::53"), ip("fe80::1")]
+        );
+    }
+
+    #[test]
+    fn parse_empty_file() {
+        assert!(parse_nameservers("").is_empty());
+    }
+}
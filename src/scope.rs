@@ -0,0 +1,97 @@
+//! Classification of addresses by their reachability scope.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Scope of an address assigned to a network interface
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddrScope {
+    /// Loopback address (`127.0.0.0/8` or `::1`)
+    Loopback,
+    /// Link-local address (`169.254.0.0/16` or `fe80::/10`), only reachable
+    /// from the link the interface is attached to
+    LinkLocal,
+    /// Private address (`10.0.0.0/8`, `172.16.0.0/12`, `192.168.0.0/16` or
+    /// the IPv6 unique-local `fc00::/7`), not routed on the Internet
+    Private,
+    /// Any other address
+    Global,
+}
+
+/// Classifies an address by its scope
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::{addr_scope, AddrScope};
+///
+/// assert_eq!(addr_scope(&"169.254.10.1".parse().unwrap()), AddrScope::LinkLocal);
+/// assert_eq!(addr_scope(&"fd12:3456::1".parse().unwrap()), AddrScope::Private);
+/// assert_eq!(addr_scope(&"2001:db8::1".parse().unwrap()), AddrScope::Global);
+/// ```
+pub fn addr_scope(addr: &IpAddr) -> AddrScope {
+    match addr {
+        IpAddr::V4(addr) => ipv4_scope(addr),
+        IpAddr::V6(addr) => ipv6_scope(addr),
+    }
+}
+
+fn ipv4_scope(addr: &Ipv4Addr) -> AddrScope {
+    if addr.is_loopback() {
+        AddrScope::Loopback
+    } else if addr.is_link_local() {
+        AddrScope::LinkLocal
+    } else if addr.is_private() {
+        AddrScope::Private
+    } else {
+        AddrScope::Global
+    }
+}
+
+fn ipv6_scope(addr: &Ipv6Addr) -> AddrScope {
+    let first_segment = addr.segments()[0];
+
+    if addr.is_loopback() {
+        AddrScope::Loopback
+    } else if crate::is_link_local(&IpAddr::V6(*addr)) {
+        AddrScope::LinkLocal
+    } else if first_segment & 0xfe00 == 0xfc00 {
+        AddrScope::Private
+    } else {
+        AddrScope::Global
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::{addr_scope, AddrScope};
+
+    fn scope(addr: &str) -> AddrScope {
+        addr_scope(&addr.parse::<IpAddr>().unwrap())
+    }
+
+    #[test]
+    fn classify_ipv4_addresses() {
+        assert_eq!(scope("127.0.0.1"), AddrScope::Loopback);
+        assert_eq!(scope("127.255.0.1"), AddrScope::Loopback);
+        assert_eq!(scope("169.254.0.1"), AddrScope::LinkLocal);
+        assert_eq!(scope("10.1.2.3"), AddrScope::Private);
+        assert_eq!(scope("172.16.0.1"), AddrScope::Private);
+        assert_eq!(scope("172.31.255.254"), AddrScope::Private);
+        assert_eq!(scope("192.168.1.10"), AddrScope::Private);
+        assert_eq!(scope("172.32.0.1"), AddrScope::Global);
+        assert_eq!(scope("8.8.8.8"), AddrScope::Global);
+    }
+
+    #[test]
+    fn classify_ipv6_addresses() {
+        assert_eq!(scope("::1"), AddrScope::Loopback);
+        assert_eq!(scope("fe80::1"), AddrScope::LinkLocal);
+        assert_eq!(scope("febf::1"), AddrScope::LinkLocal);
+        assert_eq!(scope("fc00::1"), AddrScope::Private);
+        assert_eq!(scope("fd12:3456:789a::1"), AddrScope::Private);
+        assert_eq!(scope("fec0::1"), AddrScope::Global);
+        assert_eq!(scope("2001:db8::1"), AddrScope::Global);
+    }
+}
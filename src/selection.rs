@@ -0,0 +1,345 @@
+//! Source address selection based on [RFC 6724][RFC6724].
+//!
+//! [RFC6724]: https://www.rfc-editor.org/rfc/rfc6724
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::Error;
+
+/// Scope of an address as defined in RFC 6724 Section 3.1
+const SCOPE_LINK_LOCAL: u8 = 0x2;
+const SCOPE_SITE_LOCAL: u8 = 0x5;
+const SCOPE_GLOBAL: u8 = 0xe;
+
+/// Retrieves the system's local addresses which are candidates to be used as
+/// source address when connecting to `dest`, sorted by preference as described
+/// by RFC 6724 Section 5.
+///
+/// Only addresses of the same family as `dest` are returned. The following
+/// rules are applied in order, addresses which compare equal keep the order
+/// reported by the operative system:
+///
+/// - Rule 1: Prefer the same address as `dest`
+/// - Rule 2: Prefer the appropriate scope
+/// - Rule 6: Prefer matching label, using the default policy table
+/// - Rule 8: Prefer the longest matching prefix
+///
+/// Rules depending on data which is not gathered by this crate (deprecated,
+/// home and temporary addresses or the outgoing interface) are skipped. The
+/// matching prefix is capped to the prefix length of the source address, as
+/// reported in [`InterfaceAddr::prefix_len`](crate::InterfaceAddr::prefix_len).
+/// Unlike RFC 6724, which only describes IPv6 source addresses, rule 8 is
+/// applied to IPv4 addresses as well so the address on the same subnet as
+/// `dest` is preferred, as glibc does.
+///
+/// # Example
+///
+/// ```
+/// use std::net::{IpAddr, Ipv4Addr};
+/// use local_ip_address::source_candidates;
+///
+/// let dest = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+///
+/// if let Ok(candidates) = source_candidates(dest) {
+///     println!("Preferred source address: {:?}", candidates.first());
+/// }
+/// ```
+pub fn source_candidates(dest: IpAddr) -> Result<Vec<IpAddr>, Error> {
+    let mut candidates: Vec<(IpAddr, u8)> = Vec::new();
+
+    for ifa in crate::list_afinet_netifas_info()? {
+        if ifa.addr.is_ipv4() == dest.is_ipv4()
+            && !candidates.iter().any(|(addr, _)| *addr == ifa.addr)
+        {
+            candidates.push((ifa.addr, ifa.prefix_len));
+        }
+    }
+
+    sort_candidates(&mut candidates, &dest);
+
+    Ok(candidates.into_iter().map(|(addr, _)| addr).collect())
+}
+
+/// Selects the interface address `local_ip` returns among the addresses of the
+/// system's network interfaces, loopback, down and mobile data interfaces are
+/// skipped.
+///
+/// The interface holding `routed_ip`, the source address the routing table
+/// selects towards an external address, is preferred. The primary address of
+/// such interface is returned even if `routed_ip` is one of its secondary
+/// (alias) addresses. The first primary address is returned when no interface
+/// holds `routed_ip`.
+#[cfg(getifaddrs_backend)]
+pub(crate) fn select_local_ifa(
+    ifas: &[crate::InterfaceAddr],
+    routed_ip: Option<IpAddr>,
+) -> Option<&crate::InterfaceAddr> {
+    let candidates: Vec<&crate::InterfaceAddr> = ifas
+        .iter()
+        .filter(|ifa| !ifa.is_loopback && ifa.is_up && !ifa.is_mobile_data())
+        .collect();
+
+    let routed = candidates
+        .iter()
+        .find(|ifa| Some(ifa.addr) == routed_ip)
+        .copied();
+
+    match routed {
+        Some(routed) => candidates
+            .iter()
+            .find(|ifa| {
+                ifa.is_primary
+                    && ifa.name == routed.name
+                    && ifa.addr.is_ipv4() == routed.addr.is_ipv4()
+            })
+            .copied()
+            .or(Some(routed)),
+        None => candidates.iter().find(|ifa| ifa.is_primary).copied(),
+    }
+}
+
+/// Sorts the candidate source addresses, paired with their prefix length, by
+/// preference towards `dest`
+fn sort_candidates(candidates: &mut [(IpAddr, u8)], dest: &IpAddr) {
+    let dest_scope = scope(dest);
+    let dest_label = label(dest);
+
+    candidates.sort_by_key(|(source, prefix_len)| {
+        let source_scope = scope(source);
+
+        // Rule 2: If Scope(SA) < Scope(SB): If Scope(SA) < Scope(D), then prefer
+        // SB and otherwise prefer SA.
+        let scope_key = if source_scope < dest_scope {
+            (true, u8::MAX - source_scope)
+        } else {
+            (false, source_scope)
+        };
+
+        (
+            source != dest,
+            scope_key,
+            label(source) != dest_label,
+            std::cmp::Reverse(common_prefix_len(source, *prefix_len, dest)),
+        )
+    });
+}
+
+/// Retrieves the scope of an address as described in RFC 6724 Section 3.1 and
+/// Section 3.2 for IPv4 addresses
+fn scope(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(addr) => {
+            if addr.is_loopback() || addr.is_link_local() {
+                SCOPE_LINK_LOCAL
+            } else {
+                SCOPE_GLOBAL
+            }
+        }
+        IpAddr::V6(addr) => {
+            if addr.is_multicast() {
+                (addr.segments()[0] & 0x000f) as u8
+            } else if addr.is_loopback() || crate::is_link_local(&IpAddr::V6(*addr)) {
+                SCOPE_LINK_LOCAL
+            } else if (addr.segments()[0] & 0xffc0) == 0xfec0 {
+                SCOPE_SITE_LOCAL
+            } else {
+                SCOPE_GLOBAL
+            }
+        }
+    }
+}
+
+/// Retrieves the label of an address from the default policy table described
+/// in RFC 6724 Section 2.1
+fn label(addr: &IpAddr) -> u8 {
+    let addr = match addr {
+        IpAddr::V4(addr) => return label_v4(addr),
+        IpAddr::V6(addr) => addr,
+    };
+
+    if let Some(addr) = to_ipv4_mapped(addr) {
+        return label_v4(&addr);
+    }
+
+    let segments = addr.segments();
+
+    if addr.is_loopback() {
+        0
+    } else if segments[0] == 0x2002 {
+        2
+    } else if segments[0] == 0x2001 && segments[1] == 0 {
+        5
+    } else if (segments[0] & 0xfe00) == 0xfc00 {
+        13
+    } else if segments[..6].iter().all(|segment| *segment == 0) {
+        3
+    } else if (segments[0] & 0xffc0) == 0xfec0 {
+        11
+    } else if segments[0] == 0x3ffe {
+        12
+    } else {
+        1
+    }
+}
+
+/// IPv4 addresses are represented as IPv4-mapped addresses (`::ffff:0:0/96`)
+/// in the policy table
+fn label_v4(_: &Ipv4Addr) -> u8 {
+    4
+}
+
+/// Converts an IPv4-mapped IPv6 address (`::ffff:a.b.c.d`) into its IPv4 form
+fn to_ipv4_mapped(addr: &Ipv6Addr) -> Option<Ipv4Addr> {
+    match addr.octets() {
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, a, b, c, d] => Some(Ipv4Addr::new(a, b, c, d)),
+        _ => None,
+    }
+}
+
+/// Calculates the length of the longest prefix shared by both addresses, up to
+/// the prefix length of the source address
+fn common_prefix_len(source: &IpAddr, prefix_len: u8, dest: &IpAddr) -> u32 {
+    let common_prefix_len = match (source, dest) {
+        (IpAddr::V4(source), IpAddr::V4(dest)) => {
+            (u32::from(*source) ^ u32::from(*dest)).leading_zeros()
+        }
+        (IpAddr::V6(source), IpAddr::V6(dest)) => {
+            (u128::from(*source) ^ u128::from(*dest)).leading_zeros()
+        }
+        _ => 0,
+    };
+
+    common_prefix_len.min(u32::from(prefix_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use super::sort_candidates;
+
+    fn v6(addr: &str) -> IpAddr {
+        IpAddr::V6(addr.parse::<Ipv6Addr>().unwrap())
+    }
+
+    fn v4(addr: &str) -> IpAddr {
+        IpAddr::V4(addr.parse::<Ipv4Addr>().unwrap())
+    }
+
+    #[test]
+    #[cfg(getifaddrs_backend)]
+    fn selects_primary_address_of_routed_interface() {
+        use super::select_local_ifa;
+        use crate::{InterfaceAddr, InterfaceKind};
+
+        let ifa = |name: &str, addr: &str, is_primary: bool| InterfaceAddr {
+            name: String::from(name),
+            addr: v4(addr),
+            is_loopback: name == "lo0",
+            is_primary,
+            is_up: true,
+            is_multicast: true,
+            prefix_len: 24,
+            kind: InterfaceKind::Unknown,
+        };
+        let ifas = vec![
+            ifa("lo0", "127.0.0.1", true),
+            ifa("bridge0", "172.17.0.1", true),
+            ifa("en0", "192.168.1.10", true),
+            ifa("en0", "192.168.1.20", false),
+        ];
+
+        let selected =
+            |routed_ip: Option<IpAddr>| select_local_ifa(&ifas, routed_ip).map(|ifa| ifa.addr);
+
+        assert_eq!(selected(Some(v4("192.168.1.10"))), Some(v4("192.168.1.10")));
+        assert_eq!(selected(Some(v4("192.168.1.20"))), Some(v4("192.168.1.10")));
+        assert_eq!(selected(Some(v4("127.0.0.1"))), Some(v4("172.17.0.1")));
+        assert_eq!(selected(None), Some(v4("172.17.0.1")));
+        assert_eq!(select_local_ifa(&ifas[..1], None), None);
+    }
+
+    /// Sorts the candidates towards `dest`, IPv4 addresses are given a `/24`
+    /// prefix and IPv6 addresses a `/64` prefix
+    fn sorted(candidates: Vec<IpAddr>, dest: IpAddr) -> Vec<IpAddr> {
+        let mut candidates: Vec<(IpAddr, u8)> = candidates
+            .into_iter()
+            .map(|addr| (addr, if addr.is_ipv4() { 24 } else { 64 }))
+            .collect();
+
+        sort_candidates(&mut candidates, &dest);
+
+        candidates.into_iter().map(|(addr, _)| addr).collect()
+    }
+
+    #[test]
+    fn prefers_same_address() {
+        assert_eq!(
+            sorted(
+                vec![v6("2001:db8:1::2"), v6("2001:db8:1::1")],
+                v6("2001:db8:1::1")
+            ),
+            vec![v6("2001:db8:1::1"), v6("2001:db8:1::2")]
+        );
+    }
+
+    #[test]
+    fn prefers_appropriate_scope() {
+        assert_eq!(
+            sorted(
+                vec![v6("fe80::1"), v6("2001:db8:1::1")],
+                v6("2001:db8:3::1")
+            ),
+            vec![v6("2001:db8:1::1"), v6("fe80::1")]
+        );
+        assert_eq!(
+            sorted(vec![v6("2001:db8:1::1"), v6("fe80::1")], v6("fe80::2")),
+            vec![v6("fe80::1"), v6("2001:db8:1::1")]
+        );
+    }
+
+    #[test]
+    fn prefers_matching_label() {
+        assert_eq!(
+            sorted(
+                vec![v6("2002:c633:6401::1"), v6("2001:db8:1::1")],
+                v6("2001:db8:1::2")
+            ),
+            vec![v6("2001:db8:1::1"), v6("2002:c633:6401::1")]
+        );
+    }
+
+    #[test]
+    fn prefers_longest_matching_prefix() {
+        assert_eq!(
+            sorted(
+                vec![v4("10.0.0.5"), v4("192.168.1.10"), v4("169.254.3.4")],
+                v4("192.168.1.1")
+            ),
+            vec![v4("192.168.1.10"), v4("10.0.0.5"), v4("169.254.3.4")]
+        );
+    }
+
+    #[test]
+    fn caps_matching_prefix_to_source_prefix_len() {
+        let dest = v4("192.168.1.129");
+
+        // both share the /24 subnet with `dest`, the extra bit shared by the
+        // second one lies outside of its prefix
+        let mut candidates = vec![(v4("192.168.1.10"), 24), (v4("192.168.1.200"), 24)];
+        sort_candidates(&mut candidates, &dest);
+        assert_eq!(candidates[0].0, v4("192.168.1.10"));
+
+        let mut candidates = vec![(v4("192.168.1.10"), 24), (v4("192.168.1.200"), 25)];
+        sort_candidates(&mut candidates, &dest);
+        assert_eq!(candidates[0].0, v4("192.168.1.200"));
+
+        let mut candidates = vec![(v6("2001:db8::1"), 64), (v6("2001:db8::1:0:0:1"), 64)];
+        sort_candidates(&mut candidates, &v6("2001:db8::1:0:0:2"));
+        assert_eq!(candidates[0].0, v6("2001:db8::1"));
+
+        let mut candidates = vec![(v6("2001:db8::1"), 64), (v6("2001:db8::1:0:0:1"), 112)];
+        sort_candidates(&mut candidates, &v6("2001:db8::1:0:0:2"));
+        assert_eq!(candidates[0].0, v6("2001:db8::1:0:0:1"));
+    }
+}
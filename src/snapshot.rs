@@ -0,0 +1,209 @@
+//! Polling-based detection of changes on the system's network interfaces.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use crate::{Error, InterfaceAddr};
+
+/// Addresses of the system's network interfaces captured at a point in time.
+///
+/// Two snapshots can be compared with [`Snapshot::diff`] to find which network
+/// interfaces were added, removed or had their addresses changed in between.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::Snapshot;
+///
+/// let before = Snapshot::capture().unwrap();
+/// // ...
+/// let after = Snapshot::capture().unwrap();
+///
+/// for change in before.diff(&after) {
+///     println!("{:?}", change);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    interfaces: Vec<InterfaceAddr>,
+}
+
+/// Change on a network interface found when comparing two [`Snapshot`]s
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// The network interface is only present in the newer snapshot
+    Added { name: String, addrs: Vec<IpAddr> },
+    /// The network interface is only present in the older snapshot
+    Removed { name: String, addrs: Vec<IpAddr> },
+    /// The network interface is present in both snapshots with different
+    /// addresses
+    AddrChanged {
+        name: String,
+        added: Vec<IpAddr>,
+        removed: Vec<IpAddr>,
+    },
+}
+
+impl Snapshot {
+    /// Captures the addresses of the system's network interfaces as returned
+    /// by `list_afinet_netifas_info`
+    pub fn capture() -> Result<Snapshot, Error> {
+        Ok(Snapshot::from(crate::list_afinet_netifas_info()?))
+    }
+
+    /// Retrieves the interface addresses held by the snapshot
+    pub fn interfaces(&self) -> &[InterfaceAddr] {
+        &self.interfaces
+    }
+
+    /// Compares the snapshot with a newer one, `other`, and retrieves the
+    /// changes on each network interface sorted by interface name.
+    ///
+    /// Only network interfaces holding addresses are part of a snapshot,
+    /// therefore a network interface losing all of its addresses is reported
+    /// as removed.
+    pub fn diff(&self, other: &Snapshot) -> Vec<Change> {
+        let before = self.grouped();
+        let after = other.grouped();
+        let mut changes = Vec::new();
+
+        for (name, addrs) in before.iter() {
+            match after.get(name) {
+                None => changes.push(Change::Removed {
+                    name: name.to_string(),
+                    addrs: addrs.clone(),
+                }),
+                Some(new_addrs) => {
+                    let added: Vec<IpAddr> = new_addrs
+                        .iter()
+                        .filter(|addr| !addrs.contains(addr))
+                        .copied()
+                        .collect();
+                    let removed: Vec<IpAddr> = addrs
+                        .iter()
+                        .filter(|addr| !new_addrs.contains(addr))
+                        .copied()
+                        .collect();
+
+                    if !added.is_empty() || !removed.is_empty() {
+                        changes.push(Change::AddrChanged {
+                            name: name.to_string(),
+                            added,
+                            removed,
+                        });
+                    }
+                }
+            }
+        }
+
+        for (name, addrs) in after.iter() {
+            if !before.contains_key(name) {
+                changes.push(Change::Added {
+                    name: name.to_string(),
+                    addrs: addrs.clone(),
+                });
+            }
+        }
+
+        changes.sort_by(|a, b| a.name().cmp(b.name()));
+        changes
+    }
+
+    /// Groups the addresses held by the snapshot by interface name
+    fn grouped(&self) -> BTreeMap<&str, Vec<IpAddr>> {
+        let mut grouped: BTreeMap<&str, Vec<IpAddr>> = BTreeMap::new();
+
+        for ifa in self.interfaces.iter() {
+            grouped.entry(&ifa.name).or_default().push(ifa.addr);
+        }
+
+        grouped
+    }
+}
+
+impl From<Vec<InterfaceAddr>> for Snapshot {
+    fn from(interfaces: Vec<InterfaceAddr>) -> Self {
+        Snapshot { interfaces }
+    }
+}
+
+impl Change {
+    /// Retrieves the name of the network interface the change refers to
+    pub fn name(&self) -> &str {
+        match self {
+            Change::Added { name, .. } => name,
+            Change::Removed { name, .. } => name,
+            Change::AddrChanged { name, .. } => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::{Change, Snapshot};
+    use crate::{InterfaceAddr, InterfaceKind};
+
+    fn ifa(name: &str, addr: &str) -> InterfaceAddr {
+        InterfaceAddr {
+            name: String::from(name),
+            addr: addr.parse().unwrap(),
+            is_loopback: false,
+            is_primary: true,
+            is_up: true,
+            is_multicast: true,
+            prefix_len: 24,
+            kind: InterfaceKind::Ethernet,
+        }
+    }
+
+    fn ip(addr: &str) -> IpAddr {
+        addr.parse().unwrap()
+    }
+
+    #[test]
+    fn diff_same_snapshot() {
+        let snapshot = Snapshot::from(vec![ifa("eth0", "192.168.1.10")]);
+
+        assert_eq!(snapshot.diff(&snapshot.clone()), vec![]);
+    }
+
+    #[test]
+    fn diff_added_and_removed_interfaces() {
+        let before = Snapshot::from(vec![ifa("eth0", "192.168.1.10"), ifa("tun0", "10.8.0.2")]);
+        let after = Snapshot::from(vec![
+            ifa("eth0", "192.168.1.10"),
+            ifa("wlan0", "192.168.1.20"),
+        ]);
+
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                Change::Removed {
+                    name: String::from("tun0"),
+                    addrs: vec![ip("10.8.0.2")],
+                },
+                Change::Added {
+                    name: String::from("wlan0"),
+                    addrs: vec![ip("192.168.1.20")],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_changed_addresses() {
+        let before = Snapshot::from(vec![ifa("eth0", "192.168.1.10"), ifa("eth0", "fe80::1")]);
+        let after = Snapshot::from(vec![ifa("eth0", "192.168.1.11"), ifa("eth0", "fe80::1")]);
+
+        assert_eq!(
+            before.diff(&after),
+            vec![Change::AddrChanged {
+                name: String::from("eth0"),
+                added: vec![ip("192.168.1.11")],
+                removed: vec![ip("192.168.1.10")],
+            }]
+        );
+    }
+}
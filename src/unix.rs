@@ -1,11 +1,31 @@
-use std::alloc::{alloc, dealloc, Layout};
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::mem::MaybeUninit;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+))]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use libc::{
-    getifaddrs, strlen, c_char, ifaddrs, sockaddr_in, sockaddr_in6, AF_INET, AF_INET6, IFF_LOOPBACK,
+    if_indextoname, if_nametoindex, strlen, c_char, sockaddr, sockaddr_in, sockaddr_in6, AF_INET,
+    AF_INET6, IFF_BROADCAST, IFF_LOOPBACK, IFF_MULTICAST, IFF_POINTOPOINT, IFF_RUNNING, IFF_UP,
 };
+#[cfg(not(target_os = "aix"))]
+use libc::{freeifaddrs, getifaddrs, ifaddrs};
+
+#[cfg(target_os = "aix")]
+use self::aix::{freeifaddrs, getifaddrs, ifaddrs};
 
-use crate::Error;
+use crate::interface::mac_from_bytes;
+use crate::{
+    AddrFamily, DestAddr, Error, InterfaceAddr, InterfaceFlags, InterfaceKind, NetworkInterface,
+};
 
 /// `ifaddrs` struct raw pointer alias
 type IfAddrsPtr = *mut *mut ifaddrs;
@@ -30,105 +50,911 @@ type IfAddrsPtr = *mut *mut ifaddrs;
 /// }
 /// ```
 pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
-    match list_afinet_netifas_info() {
-        Ok(interfaces) => Ok(interfaces
-            .iter()
-            .map(|i| (i.iname.clone(), i.addr))
-            .collect()),
+    match list_afinet_netifas_info_impl(AddrFamily::Unspec) {
+        Ok(interfaces) => {
+            Ok(crate::dedup_netifas(interfaces.into_iter().map(|i| (i.name, i.addr))).collect())
+        }
         Err(e) => Err(e),
     }
 }
 
-pub(crate) struct AfInetInfo {
-    pub addr: IpAddr,
-    pub iname: String,
-    pub is_loopback: bool,
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas`] does, yielding the addresses lazily.
+///
+/// The `getifaddrs` list is walked as the iterator is advanced and released
+/// with `freeifaddrs` once the iterator is dropped. Interface names which are
+/// not valid UTF-8 are converted lossily instead of failing the search. Unlike
+/// [`list_afinet_netifas`], exact `(name, ip)` duplicates the system reports are
+/// yielded as they are, so nothing is allocated besides the names.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::iter_afinet_netifas;
+///
+/// let first = iter_afinet_netifas().unwrap().find(|(_, ip)| ip.is_ipv4());
+///
+/// println!("First IPv4 address: {:?}", first);
+/// ```
+pub fn iter_afinet_netifas() -> Result<impl Iterator<Item = (String, IpAddr)>, Error> {
+    let ifaddrs = IfAddrs::new()?;
+
+    Ok(AfinetNetifasIter {
+        next: ifaddrs.head,
+        _ifaddrs: ifaddrs,
+    })
+}
+
+/// Perform a search over the system's network interfaces using `getifaddrs`,
+/// each address belonging to the `AF_INET` or `AF_INET6` family is retrieved
+/// along with its prefix length and the index and MAC address of its interface.
+///
+/// The prefix length is read from `ifa_netmask`, and is `None` when no netmask
+/// is reported. The index is retrieved through `if_nametoindex`. The MAC address
+/// is read from the link-layer node (`AF_LINK`, or `AF_PACKET` on Android) the
+/// list holds for the interface. The flags are read from `ifa_flags`. The MTU
+/// is retrieved through a `SIOCGIFMTU` `ioctl`, which is only available on
+/// Linux-based systems, macOS, iOS, FreeBSD and OpenBSD.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_network_interfaces;
+///
+/// for ifa in list_network_interfaces().unwrap() {
+///     println!("{} ({:?}):\t{:?}/{:?}", ifa.name, ifa.index, ifa.addr, ifa.prefix_len);
+/// }
+/// ```
+pub fn list_network_interfaces() -> Result<Vec<NetworkInterface>, Error> {
+    let mut interfaces: Vec<NetworkInterface> = Vec::new();
+    let mut macs: HashMap<String, [u8; 6]> = HashMap::new();
+    // the `ioctl` requests are issued on a datagram socket, the MTU is not
+    // reported if it can't be opened
+    let socket = unsafe { libc::socket(AF_INET, libc::SOCK_DGRAM, 0) };
+
+    let result = walk_ifaddrs(|ifa| unsafe {
+        if let Some(mac) = get_ifa_mac(ifa) {
+            macs.insert(get_ifa_name(ifa)?, mac);
+        }
+
+        if let Some(addr) = get_ifa_addr(ifa) {
+            let name = get_ifa_name(ifa)?;
+            // interfaces holding several addresses are looked up once
+            let (index, mtu) = match interfaces.iter().find(|interface| interface.name == name) {
+                Some(interface) => (interface.index, interface.mtu),
+                None if is_loopback_addr(ifa) => (interface_index(&name).ok(), None),
+                None => (interface_index(&name).ok(), interface_mtu(socket, &name)),
+            };
+
+            interfaces.push(NetworkInterface {
+                name,
+                addr,
+                prefix_len: get_ifa_netmask_prefix_len(ifa, &addr),
+                is_loopback: is_loopback_addr(ifa),
+                index,
+                mac: None,
+                mtu,
+                flags: get_ifa_flags(ifa),
+                kind: if is_loopback_addr(ifa) {
+                    InterfaceKind::Loopback
+                } else {
+                    InterfaceKind::Unknown
+                },
+                scope_id: if crate::is_link_local(&addr) {
+                    get_ifa_scope_id(ifa).or(index)
+                } else {
+                    None
+                },
+                dest_addr: get_ifa_dest_addr(ifa),
+                valid_lifetime: None,
+                preferred_lifetime: None,
+            });
+        }
+
+        Ok(())
+    });
+
+    if socket >= 0 {
+        unsafe { libc::close(socket) };
+    }
+
+    result?;
+
+    // link-layer nodes are not guaranteed to precede the address nodes
+    for interface in interfaces.iter_mut() {
+        interface.mac = macs.get(&interface.name).copied();
+    }
+
+    Ok(interfaces)
+}
+
+/// Perform a search over the system's network interfaces using `getifaddrs`
+/// just as [`list_afinet_netifas`] does, each address is paired with a flag which is
+/// `true` when the address is the primary address of the interface.
+///
+/// `getifaddrs` doesn't flag alias addresses, the first address of each family
+/// listed for an interface is considered its primary address.
+pub fn list_afinet_netifas_with_primary() -> Result<Vec<(String, IpAddr, bool)>, Error> {
+    let ifas = list_afinet_netifas_info()?;
+
+    Ok(ifas
+        .into_iter()
+        .map(|ifa| (ifa.name, ifa.addr, ifa.is_primary))
+        .collect())
+}
+
+/// Perform a search over the system's network interfaces using `getifaddrs`,
+/// retrieved network interfaces belonging to both socket address families
+/// `AF_INET` and `AF_INET6` are retrieved along with details on the interface
+/// holding them.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_afinet_netifas_info;
+///
+/// let ifas = list_afinet_netifas_info().unwrap();
+///
+/// for ifa in ifas.iter().filter(|ifa| !ifa.is_loopback) {
+///     println!("{}:\t{:?}", ifa.name, ifa.addr);
+/// }
+/// ```
+pub fn list_afinet_netifas_info() -> Result<Vec<InterfaceAddr>, Error> {
+    list_afinet_netifas_info_impl(AddrFamily::Unspec)
+}
+
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas`] does, only retrieving addresses of the given `family`.
+///
+/// `getifaddrs` can't filter by family, addresses of other families are
+/// skipped while walking the list without allocating their interface name.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::{list_afinet_netifas_family, AddrFamily};
+///
+/// for (name, ip) in list_afinet_netifas_family(AddrFamily::Inet).unwrap() {
+///     println!("{}:\t{:?}", name, ip);
+/// }
+/// ```
+pub fn list_afinet_netifas_family(family: AddrFamily) -> Result<Vec<(String, IpAddr)>, Error> {
+    let ifas = list_afinet_netifas_info_impl(family)?;
+
+    Ok(ifas.into_iter().map(|ifa| (ifa.name, ifa.addr)).collect())
+}
+
+/// Retrieves the interface addresses of the given `family`, the primary address
+/// of each interface is determined among the addresses of that family
+pub(crate) fn list_afinet_netifas_info_impl(
+    family: AddrFamily,
+) -> Result<Vec<InterfaceAddr>, Error> {
+    let mut interfaces: Vec<InterfaceAddr> = Vec::new();
+
+    // To find the relevant interface address walk over the nodes of the
+    // linked list looking for interface address which belong to the socket
+    // address families AF_INET (IPv4) and AF_INET6 (IPv6)
+    walk_ifaddrs(|ifa| unsafe {
+        if let Some(addr) = get_ifa_addr(ifa).filter(|addr| family.contains(addr)) {
+            let name = get_ifa_name(ifa)?;
+
+            interfaces.push(InterfaceAddr {
+                is_primary: is_first_of_family(&interfaces, &name, &addr),
+                addr,
+                name,
+                is_loopback: is_loopback_addr(ifa),
+                is_up: is_up_addr(ifa),
+                is_multicast: is_multicast_addr(ifa),
+                prefix_len: get_ifa_prefix_len(ifa, &addr),
+                kind: if is_loopback_addr(ifa) {
+                    InterfaceKind::Loopback
+                } else {
+                    InterfaceKind::Unknown
+                },
+            });
+        }
+
+        Ok(())
+    })?;
+
+    Ok(interfaces)
+}
+
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas`] does, skipping malformed entries instead of failing.
+///
+/// Interface addresses whose interface name is not valid UTF-8 are skipped,
+/// their count is returned along with the entries which were retrieved. A
+/// failing `getifaddrs` call still fails the search.
+pub fn list_afinet_netifas_lenient() -> Result<(Vec<(String, IpAddr)>, usize), Error> {
+    let mut interfaces = Vec::new();
+    let mut skipped = 0;
+
+    walk_ifaddrs(|ifa| unsafe {
+        if let Some(addr) = get_ifa_addr(ifa) {
+            match get_ifa_name(ifa) {
+                Ok(name) => interfaces.push((name, addr)),
+                Err(_) => skipped += 1,
+            }
+        }
+
+        Ok(())
+    })?;
+
+    Ok((crate::dedup_netifas(interfaces).collect(), skipped))
+}
+
+/// Retrieves the IPv6 addresses of the system's network interfaces along with
+/// the flow information (`sin6_flowinfo`, traffic class and flow label) of
+/// their socket address, in host byte order.
+///
+/// Most interfaces report a zero flow information.
+pub fn list_afinet_netifas_v6_flowinfo() -> Result<Vec<(String, Ipv6Addr, u32)>, Error> {
+    let mut interfaces = Vec::new();
+
+    walk_ifaddrs(|ifa| unsafe {
+        let ifa_addr = (**ifa).ifa_addr;
+
+        if ifa_addr.is_null() || get_sa_family(ifa_addr) != AF_INET6 {
+            return Ok(());
+        }
+
+        let socket_addr_v6 = &*(ifa_addr as *const sockaddr_in6);
+        interfaces.push((
+            get_ifa_name(ifa)?,
+            Ipv6Addr::from(socket_addr_v6.sin6_addr.s6_addr),
+            u32::from_be(socket_addr_v6.sin6_flowinfo),
+        ));
+
+        Ok(())
+    })?;
+
+    Ok(interfaces)
+}
+
+/// Counts the addresses of the system's non-loopback network interfaces
+/// belonging to the `AF_INET` and `AF_INET6` families respectively.
+///
+/// The `getifaddrs` list is walked once and no interface name is allocated.
+pub fn address_family_counts() -> Result<(usize, usize), Error> {
+    let mut counts = (0, 0);
+
+    walk_ifaddrs(|ifa| unsafe {
+        if is_loopback_addr(ifa) {
+            return Ok(());
+        }
+
+        match get_ifa_addr(ifa) {
+            Some(IpAddr::V4(_)) => counts.0 += 1,
+            Some(IpAddr::V6(_)) => counts.1 += 1,
+            None => {}
+        }
+
+        Ok(())
+    })?;
+
+    Ok(counts)
+}
+
+/// Retrieves the addresses of the network interface with the given index.
+///
+/// `getifaddrs` doesn't report interface indexes, the index is resolved to the
+/// name of its interface with `if_indextoname` and the addresses are filtered
+/// by name. An empty `Vec` is returned when no interface with the given index
+/// holds an address.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::addresses_for_index;
+///
+/// for ip_address in addresses_for_index(1).unwrap() {
+///     println!("{:?}", ip_address);
+/// }
+/// ```
+pub fn addresses_for_index(index: u32) -> Result<Vec<IpAddr>, Error> {
+    let name = match interface_name(index) {
+        Ok(name) => name,
+        Err(Error::InterfaceNotFound(_)) => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    Ok(list_afinet_netifas()?
+        .into_iter()
+        .filter(|(ifname, _)| *ifname == name)
+        .map(|(_, addr)| addr)
+        .collect())
+}
+
+/// Retrieves the broadcast IPv4 address of the network interface with the
+/// given name, as held by the `ifa_broadaddr` field of its `getifaddrs` nodes.
+///
+/// Returns `Error::InterfaceNotFound` if no interface with such name exists, and
+/// `Error::LocalIpAddressNotFound` if none of its IPv4 addresses has a broadcast
+/// address, as is the case for loopback and point-to-point interfaces.
+pub fn broadcast_ip_on(name: &str) -> Result<IpAddr, Error> {
+    interface_index(name)?;
+
+    let mut broadcast_ip = None;
+
+    walk_ifaddrs(|ifa| unsafe {
+        if broadcast_ip.is_some() || CStr::from_ptr((**ifa).ifa_name).to_bytes() != name.as_bytes()
+        {
+            return Ok(());
+        }
+
+        if let Some(IpAddr::V4(_)) = get_ifa_addr(ifa) {
+            broadcast_ip = get_ifa_broadaddr(ifa);
+        }
+
+        Ok(())
+    })?;
+
+    broadcast_ip.ok_or(Error::LocalIpAddressNotFound)
+}
+
+/// Retrieves the broadcast IPv4 address of the network interface holding the
+/// local IPv4 address returned by `local_ip`, as named by
+/// `local_ip_detailed`.
+///
+/// Returns `Error::LocalIpAddressNotFound` if there is no such interface or if
+/// it has no broadcast address, as is the case for point-to-point interfaces.
+pub fn local_broadcast_ip() -> Result<IpAddr, Error> {
+    let (name, _) = crate::local_ip_detailed()?;
+
+    broadcast_ip_on(&name)
+}
+
+/// Retrieves the IPv4 address of the default gateway for this system
+///
+/// The IPv4 routing table is dumped through the `NET_RT_FLAGS` sysctl of the
+/// `PF_ROUTE` family, only keeping routes through a gateway (`RTF_GATEWAY`). The
+/// gateway of the first default route of the dump is returned.
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+pub fn default_gateway() -> Result<IpAddr, Error> {
+    let mut mib = [
+        libc::CTL_NET,
+        libc::PF_ROUTE,
+        0,
+        AF_INET,
+        libc::NET_RT_FLAGS,
+        libc::RTF_GATEWAY,
+    ];
+    let mut len: libc::size_t = 0;
+
+    // the routing table may grow between the size query and the dump
+    for _ in 0..3 {
+        let result = unsafe {
+            libc::sysctl(
+                mib.as_mut_ptr(),
+                mib.len() as libc::c_uint,
+                std::ptr::null_mut(),
+                &mut len,
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+
+        if result != 0 {
+            return Err(Error::Io(std::io::Error::last_os_error()));
+        }
+
+        let mut dump = vec![0u8; len];
+        let result = unsafe {
+            libc::sysctl(
+                mib.as_mut_ptr(),
+                mib.len() as libc::c_uint,
+                dump.as_mut_ptr() as *mut libc::c_void,
+                &mut len,
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+
+        if result == 0 {
+            dump.truncate(len);
+            return default_gateway_from_dump(&dump).ok_or(Error::LocalIpAddressNotFound);
+        }
+
+        let err = std::io::Error::last_os_error();
+
+        if err.raw_os_error() != Some(libc::ENOMEM) {
+            return Err(Error::Io(err));
+        }
+    }
+
+    Err(Error::StrategyError(String::from(
+        "The routing table kept growing while being dumped",
+    )))
+}
+
+/// Retrieves the gateway of the first IPv4 default route held by a dump of
+/// routing messages, each one made of a `rt_msghdr` followed by the socket
+/// addresses flagged in `rtm_addrs`
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+fn default_gateway_from_dump(dump: &[u8]) -> Option<IpAddr> {
+    const RTAX_DST: usize = 0;
+    const RTAX_GATEWAY: usize = 1;
+    const RTAX_NETMASK: usize = 2;
+    const RTAX_MAX: usize = 8;
+
+    // socket addresses are padded to a multiple of `u32` on Apple platforms and
+    // of `long` on FreeBSD
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    const SA_ALIGN: usize = std::mem::size_of::<u32>();
+    #[cfg(target_os = "freebsd")]
+    const SA_ALIGN: usize = std::mem::size_of::<libc::c_long>();
+
+    // `sin_addr` follows `sin_len`, `sin_family` and `sin_port`
+    let sin_addr = |sa: &[u8]| -> Option<Ipv4Addr> {
+        let bytes = sa.get(4..8)?;
+        Some(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]))
+    };
+
+    let header_len = std::mem::size_of::<libc::rt_msghdr>();
+    let mut offset = 0;
+
+    while let Some(msg) = dump.get(offset..offset + header_len) {
+        let rtm = unsafe { std::ptr::read_unaligned(msg.as_ptr() as *const libc::rt_msghdr) };
+        let msglen = usize::from(rtm.rtm_msglen);
+        let body = dump.get(offset + header_len..offset + msglen)?;
+        offset += msglen;
+
+        let mut addrs: [Option<&[u8]>; RTAX_MAX] = [None; RTAX_MAX];
+        let mut position = 0;
+
+        for (rtax, addr) in addrs.iter_mut().enumerate() {
+            if rtm.rtm_addrs & (1 << rtax) == 0 {
+                continue;
+            }
+
+            let sa_len = match body.get(position) {
+                Some(sa_len) => usize::from(*sa_len),
+                None => break,
+            };
+
+            *addr = body.get(position..(position + sa_len).min(body.len()));
+            position += if sa_len == 0 {
+                SA_ALIGN
+            } else {
+                (sa_len + SA_ALIGN - 1) & !(SA_ALIGN - 1)
+            };
+        }
+
+        let is_inet = |sa: &[u8]| sa.get(1).map(|family| i32::from(*family)) == Some(AF_INET);
+        let is_default_dst = addrs[RTAX_DST]
+            .filter(|sa| is_inet(sa))
+            .and_then(sin_addr)
+            .map(|dst| dst.is_unspecified())
+            .unwrap_or(false);
+        // the netmask of a default route is empty or truncated before `sin_addr`
+        let is_default_netmask = match addrs[RTAX_NETMASK] {
+            Some(sa) => sa.iter().skip(4).all(|byte| *byte == 0),
+            None => true,
+        };
+
+        if !is_default_dst || !is_default_netmask {
+            continue;
+        }
+
+        let gateway = addrs[RTAX_GATEWAY]
+            .filter(|sa| is_inet(sa))
+            .and_then(sin_addr);
+
+        if let Some(gateway) = gateway {
+            return Some(IpAddr::V4(gateway));
+        }
+    }
+
+    None
 }
 
-impl AfInetInfo {
-    /// Determines if an interface is used for mobile_data
-    pub(crate) fn is_mobile_data(&self) -> bool {
-        self.iname.contains("rmnet_data")
+/// Retrieves the time of the last change of each of the system's network
+/// interfaces, read from the `ifi_lastchange` field of the `if_data` statistics
+/// `getifaddrs` attaches to `AF_LINK` nodes.
+///
+/// Interfaces whose last change is unknown or before the Unix epoch are skipped.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+))]
+pub fn interface_last_change() -> Result<Vec<(String, SystemTime)>, Error> {
+    let mut changes: Vec<(String, SystemTime)> = Vec::new();
+
+    walk_ifaddrs(|ifa| unsafe {
+        let ifa_addr = (**ifa).ifa_addr;
+        let ifa_data = (**ifa).ifa_data as *const libc::if_data;
+
+        if ifa_addr.is_null() || ifa_data.is_null() || get_sa_family(ifa_addr) != libc::AF_LINK {
+            return Ok(());
+        }
+
+        // `if_data` is packed on some platforms, copy it before reading it
+        let data = std::ptr::read_unaligned(ifa_data);
+
+        #[cfg(target_os = "freebsd")]
+        let lastchange = data.__ifi_lastchange.tv;
+        #[cfg(not(target_os = "freebsd"))]
+        let lastchange = data.ifi_lastchange;
+
+        // `timeval` fields are `i32` on some platforms and `i64` on others
+        #[allow(clippy::useless_conversion)]
+        let (secs, micros) = (i64::from(lastchange.tv_sec), i64::from(lastchange.tv_usec));
+
+        if let Some(changed_at) = to_system_time(secs, micros) {
+            changes.push((get_ifa_name(ifa)?, changed_at));
+        }
+
+        Ok(())
+    })?;
+
+    Ok(changes)
+}
+
+/// Converts a `timeval` since the Unix epoch into a `SystemTime`, unset
+/// (zero) and negative times are not converted
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+))]
+fn to_system_time(secs: i64, micros: i64) -> Option<SystemTime> {
+    let secs = u64::try_from(secs).ok()?;
+    let micros = u64::try_from(micros).ok()?;
+
+    if secs == 0 && micros == 0 {
+        return None;
     }
+
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs) + Duration::from_micros(micros))
 }
 
-// Internal method to list AF_INET info in a struct.  This method is used by
-// list_afiinet_netifas and local_ip,
-pub(crate) fn list_afinet_netifas_info() -> Result<Vec<AfInetInfo>, Error> {
-    unsafe {
-        let layout = Layout::new::<IfAddrsPtr>();
-        let ptr = alloc(layout);
-        let myaddr = ptr as IfAddrsPtr;
-        let getifaddrs_result = getifaddrs(myaddr);
+/// Retrieves the system's interface addresses using `getifaddrs` and calls `f`
+/// with each node of the returned list, stopping at the first error. The list
+/// is released with `freeifaddrs` once walked, whether `f` failed or not.
+fn walk_ifaddrs<F>(f: F) -> Result<(), Error>
+where
+    F: FnMut(IfAddrsPtr) -> Result<(), Error>,
+{
+    let ifaddrs = IfAddrs::new()?;
+
+    unsafe { walk_ifaddrs_list(ifaddrs.head, f) }
+}
+
+/// List of interface addresses returned by `getifaddrs`, released with
+/// `freeifaddrs` when dropped
+struct IfAddrs {
+    head: *mut ifaddrs,
+}
+
+impl IfAddrs {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", fields(nodes = tracing::field::Empty))
+    )]
+    fn new() -> Result<IfAddrs, Error> {
+        let mut head = MaybeUninit::<*mut ifaddrs>::uninit();
+        let getifaddrs_result = unsafe { getifaddrs(head.as_mut_ptr()) };
 
         if getifaddrs_result != 0 {
-            // an error occurred on getifaddrs
-            return Err(Error::StrategyError(format!(
-                "GetIfAddrs returned error: {}",
-                getifaddrs_result
-            )));
-        }
-
-        let mut interfaces: Vec<AfInetInfo> = Vec::new();
-        let ifa = myaddr;
-
-        // An instance of `ifaddrs` is build on top of a linked list where
-        // `ifaddrs.ifa_next` represent the next node in the list.
-        //
-        // To find the relevant interface address walk over the nodes of the
-        // linked list looking for interface address which belong to the socket
-        // address families AF_INET (IPv4) and AF_INET6 (IPv6)
-        loop {
-            let ifa_addr = (**ifa).ifa_addr;
-
-            match (*ifa_addr).sa_family as i32 {
-                // AF_INET IPv4 protocol implementation
-                AF_INET => {
-                    let interface_address = ifa_addr;
-                    let socket_addr_v4: *mut sockaddr_in = interface_address as *mut sockaddr_in;
-                    let in_addr = (*socket_addr_v4).sin_addr;
-                    let mut ip_addr = Ipv4Addr::from(in_addr.s_addr);
-
-                    if cfg!(target_endian = "little") {
-                        // due to a difference on how bytes are arranged on a
-                        // single word of memory by the CPU, swap bytes based
-                        // on CPU endianness to avoid having twisted IP addresses
-                        //
-                        // refer: https://github.com/rust-lang/rust/issues/48819
-                        ip_addr = Ipv4Addr::from(in_addr.s_addr.swap_bytes());
-                    }
-
-                    interfaces.push(AfInetInfo {
-                        addr: IpAddr::V4(ip_addr),
-                        iname: get_ifa_name(ifa)?,
-                        is_loopback: is_loopback_addr(ifa),
-                    });
-                }
-                // AF_INET6 IPv6 protocol implementation
-                AF_INET6 => {
-                    let interface_address = ifa_addr;
-                    let socket_addr_v6: *mut sockaddr_in6 = interface_address as *mut sockaddr_in6;
-                    let in6_addr = (*socket_addr_v6).sin6_addr;
-                    let ip_addr = Ipv6Addr::from(in6_addr.s6_addr);
-
-                    interfaces.push(AfInetInfo {
-                        addr: IpAddr::V6(ip_addr),
-                        iname: get_ifa_name(ifa)?,
-                        is_loopback: is_loopback_addr(ifa),
-                    });
+            // an error occurred on getifaddrs, no list was allocated
+            return Err(Error::GetIfAddrs {
+                ret: getifaddrs_result,
+                errno: std::io::Error::last_os_error().raw_os_error().unwrap_or(0),
+            });
+        }
+
+        // `getifaddrs` succeeds with an empty list when the system has no
+        // network interfaces configured
+        let ifaddrs = IfAddrs {
+            head: unsafe { head.assume_init() },
+        };
+
+        #[cfg(feature = "tracing")]
+        {
+            let mut nodes = 0;
+            unsafe {
+                let _ = walk_ifaddrs_list(ifaddrs.head, |_| {
+                    nodes += 1;
+                    Ok(())
+                });
+            }
+            tracing::Span::current().record("nodes", nodes);
+        }
+
+        Ok(ifaddrs)
+    }
+}
+
+impl Drop for IfAddrs {
+    fn drop(&mut self) {
+        if !self.head.is_null() {
+            unsafe { freeifaddrs(self.head) };
+        }
+    }
+}
+
+/// Iterator over the addresses of the `getifaddrs` list, refer to
+/// [`iter_afinet_netifas`]
+struct AfinetNetifasIter {
+    next: *mut ifaddrs,
+    // keeps the nodes pointed by `next` alive
+    _ifaddrs: IfAddrs,
+}
+
+impl Iterator for AfinetNetifasIter {
+    type Item = (String, IpAddr);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.next.is_null() {
+            let mut ifa = self.next;
+
+            unsafe {
+                self.next = (*ifa).ifa_next;
+
+                if let Some(addr) = get_ifa_addr(&mut ifa) {
+                    let name = CStr::from_ptr((*ifa).ifa_name).to_string_lossy();
+                    return Some((name.into_owned(), addr));
                 }
-                _ => {}
             }
+        }
+
+        None
+    }
+}
+
+/// Calls `f` with each node of the `ifaddrs` linked list starting at `head`,
+/// stopping at the first error. A null `head` is an empty list.
+unsafe fn walk_ifaddrs_list<F>(head: *mut ifaddrs, mut f: F) -> Result<(), Error>
+where
+    F: FnMut(IfAddrsPtr) -> Result<(), Error>,
+{
+    let mut ifa = head;
+
+    // An instance of `ifaddrs` is build on top of a linked list where
+    // `ifaddrs.ifa_next` represent the next node in the list.
+    while !ifa.is_null() {
+        f(&mut ifa)?;
+        ifa = (*ifa).ifa_next;
+    }
+
+    Ok(())
+}
+
+/// Retrieves the index of the network interface with the given name using
+/// `if_nametoindex`
+pub(crate) fn interface_index(name: &str) -> Result<u32, Error> {
+    let c_name = CString::new(name).map_err(|_| Error::InterfaceNotFound(String::from(name)))?;
+    let index = unsafe { if_nametoindex(c_name.as_ptr()) };
+
+    if index == 0 {
+        return Err(Error::InterfaceNotFound(String::from(name)));
+    }
+
+    Ok(index)
+}
+
+/// Retrieves the name of the network interface with the given index using
+/// `if_indextoname`
+fn interface_name(index: u32) -> Result<String, Error> {
+    let mut ifname = [0 as c_char; libc::IF_NAMESIZE];
+    let ptr = unsafe { if_indextoname(index, ifname.as_mut_ptr()) };
+
+    if ptr.is_null() {
+        return Err(Error::InterfaceNotFound(index.to_string()));
+    }
+
+    let ifname = unsafe { CStr::from_ptr(ifname.as_ptr()) };
+
+    Ok(ifname.to_string_lossy().into_owned())
+}
+
+/// Retrieves the MTU of the network interface with the given name through a
+/// `SIOCGIFMTU` `ioctl` issued on `socket`
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd",
+))]
+fn interface_mtu(socket: libc::c_int, name: &str) -> Option<u32> {
+    // `libc` doesn't define the request on FreeBSD and OpenBSD, both encode it
+    // as `_IOWR('i', n, struct ifreq)`
+    #[cfg(target_os = "freebsd")]
+    const SIOCGIFMTU: libc::c_ulong = 0xc020_6933;
+    #[cfg(target_os = "openbsd")]
+    const SIOCGIFMTU: libc::c_ulong = 0xc020_697e;
+    #[cfg(not(any(target_os = "freebsd", target_os = "openbsd")))]
+    use libc::SIOCGIFMTU;
+
+    if socket < 0 {
+        return None;
+    }
+
+    let mut ifr: libc::ifreq = unsafe { std::mem::zeroed() };
+
+    // the name must leave room for the nul terminator
+    if name.len() >= ifr.ifr_name.len() {
+        return None;
+    }
+
+    for (dst, src) in ifr.ifr_name.iter_mut().zip(name.bytes()) {
+        *dst = c_char::from_ne_bytes([src]);
+    }
+
+    if unsafe { libc::ioctl(socket, SIOCGIFMTU as _, &mut ifr) } < 0 {
+        return None;
+    }
+
+    let mtu = unsafe { ifr.ifr_ifru.ifru_mtu };
+
+    u32::try_from(mtu).ok().filter(|mtu| *mtu > 0)
+}
+
+/// Retrieves the MTU of the network interface with the given name, `SIOCGIFMTU`
+/// is not available on this platform
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd",
+)))]
+fn interface_mtu(_socket: libc::c_int, _name: &str) -> Option<u32> {
+    None
+}
+
+/// Retrieves the address family of a socket address.
+///
+/// `sa_family_t` is an `u8` on BSD-based systems and an `u16` on Linux-based
+/// ones, widening it through `From` keeps the read lossless on both.
+unsafe fn get_sa_family(addr: *const sockaddr) -> i32 {
+    i32::from((*addr).sa_family)
+}
+
+/// Retrieves the address of an interface address if it belongs to the socket
+/// address families `AF_INET` (IPv4) or `AF_INET6` (IPv6).
+///
+/// Nodes without an address, which is common for the first node of the list,
+/// are skipped.
+unsafe fn get_ifa_addr(ifa: *mut *mut ifaddrs) -> Option<IpAddr> {
+    get_sockaddr_ip((**ifa).ifa_addr)
+}
+
+/// Retrieves the MAC address held by a link-layer node of the list, these
+/// nodes carry a `sockaddr_dl` (`AF_LINK`) on BSD-based systems and AIX, and a
+/// `sockaddr_ll` (`AF_PACKET`) on Linux-based systems such as Android.
+unsafe fn get_ifa_mac(ifa: *mut *mut ifaddrs) -> Option<[u8; 6]> {
+    let ifa_addr = (**ifa).ifa_addr;
+
+    if ifa_addr.is_null() {
+        return None;
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        if get_sa_family(ifa_addr) != libc::AF_PACKET {
+            return None;
+        }
+
+        let sll = &*(ifa_addr as *const libc::sockaddr_ll);
+        let len = usize::from(sll.sll_halen).min(sll.sll_addr.len());
+
+        mac_from_bytes(&sll.sll_addr[..len])
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+        if get_sa_family(ifa_addr) != libc::AF_LINK {
+            return None;
+        }
+
+        // the hardware address follows the interface name in `sdl_data`, which
+        // may extend past the declared size of the array
+        let sdl = ifa_addr as *const libc::sockaddr_dl;
+        let lladdr =
+            (std::ptr::addr_of!((*sdl).sdl_data) as *const u8).add(usize::from((*sdl).sdl_nlen));
+
+        mac_from_bytes(std::slice::from_raw_parts(
+            lladdr,
+            usize::from((*sdl).sdl_alen),
+        ))
+    }
+}
+
+/// Retrieves the scope id (`sin6_scope_id`) of an `AF_INET6` interface address,
+/// `None` when it's not set
+unsafe fn get_ifa_scope_id(ifa: *mut *mut ifaddrs) -> Option<u32> {
+    let ifa_addr = (**ifa).ifa_addr;
+
+    if ifa_addr.is_null() || get_sa_family(ifa_addr) != AF_INET6 {
+        return None;
+    }
+
+    let scope_id = (*(ifa_addr as *const sockaddr_in6)).sin6_scope_id;
+
+    Some(scope_id).filter(|scope_id| *scope_id != 0)
+}
+
+/// Retrieves the broadcast address of an interface address, only available
+/// when the interface has the `IFF_BROADCAST` flag set.
+///
+/// `ifa_broadaddr` is a C macro aliasing the `ifa_dstaddr` field on BSD-based
+/// systems and AIX, Linux-based systems such as Android name the union holding
+/// it `ifa_ifu`.
+unsafe fn get_ifa_broadaddr(ifa: *mut *mut ifaddrs) -> Option<IpAddr> {
+    let iflags = (*(*ifa)).ifa_flags as i32;
 
-            // Check if we are at the end of our network interface list
-            *ifa = (**ifa).ifa_next;
-            if (*ifa).is_null() {
-                break;
+    if (iflags & IFF_BROADCAST) == 0 {
+        return None;
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let ifa_broadaddr = (**ifa).ifa_ifu;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    let ifa_broadaddr = (**ifa).ifa_dstaddr;
+
+    get_sockaddr_ip(ifa_broadaddr)
+}
+
+/// Retrieves the destination address of an interface address, this is the
+/// broadcast address when the interface has the `IFF_BROADCAST` flag set or the
+/// address of the peer when it has the `IFF_POINTOPOINT` flag set, both held by
+/// the same union.
+unsafe fn get_ifa_dest_addr(ifa: *mut *mut ifaddrs) -> Option<DestAddr> {
+    let iflags = (*(*ifa)).ifa_flags as i32;
+
+    if (iflags & IFF_POINTOPOINT) != 0 {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        let ifa_dstaddr = (**ifa).ifa_ifu;
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        let ifa_dstaddr = (**ifa).ifa_dstaddr;
+
+        return get_sockaddr_ip(ifa_dstaddr).map(DestAddr::PointToPoint);
+    }
+
+    get_ifa_broadaddr(ifa).map(DestAddr::Broadcast)
+}
+
+/// Retrieves the address held by a socket address if it belongs to the socket
+/// address families `AF_INET` (IPv4) or `AF_INET6` (IPv6).
+unsafe fn get_sockaddr_ip(ifa_addr: *mut sockaddr) -> Option<IpAddr> {
+    if ifa_addr.is_null() {
+        return None;
+    }
+
+    match get_sa_family(ifa_addr) {
+        // AF_INET IPv4 protocol implementation
+        AF_INET => {
+            let socket_addr_v4: *mut sockaddr_in = ifa_addr as *mut sockaddr_in;
+            let in_addr = (*socket_addr_v4).sin_addr;
+            let mut ip_addr = Ipv4Addr::from(in_addr.s_addr);
+
+            if cfg!(target_endian = "little") {
+                // due to a difference on how bytes are arranged on a
+                // single word of memory by the CPU, swap bytes based
+                // on CPU endianness to avoid having twisted IP addresses
+                //
+                // refer: https://github.com/rust-lang/rust/issues/48819
+                ip_addr = Ipv4Addr::from(in_addr.s_addr.swap_bytes());
             }
+
+            Some(IpAddr::V4(ip_addr))
         }
+        // AF_INET6 IPv6 protocol implementation
+        AF_INET6 => {
+            let socket_addr_v6: *mut sockaddr_in6 = ifa_addr as *mut sockaddr_in6;
+            let in6_addr = (*socket_addr_v6).sin6_addr;
 
-        dealloc(ptr, layout);
-        Ok(interfaces)
+            Some(IpAddr::V6(Ipv6Addr::from(in6_addr.s6_addr)))
+        }
+        _ => None,
     }
 }
 
@@ -146,8 +972,260 @@ unsafe fn get_ifa_name(ifa: *mut *mut ifaddrs) -> Result<String, Error> {
     }
 }
 
+/// Determines if no address of the same family was listed for the interface
+/// before, in which case the address is the primary address of the interface
+fn is_first_of_family(interfaces: &[InterfaceAddr], name: &str, addr: &IpAddr) -> bool {
+    !interfaces
+        .iter()
+        .any(|ifa| ifa.name == name && ifa.addr.is_ipv4() == addr.is_ipv4())
+}
+
+/// Determines if the interface holding an interface address supports multicast
+unsafe fn is_multicast_addr(ifa: *mut *mut ifaddrs) -> bool {
+    let iflags = (*(*ifa)).ifa_flags as i32;
+    (iflags & IFF_MULTICAST) != 0
+}
+
+/// Retrieves the length of the network prefix of an interface address from its
+/// netmask, host addresses (`/32` or `/128`) are assumed when there is no netmask
+unsafe fn get_ifa_prefix_len(ifa: *mut *mut ifaddrs, addr: &IpAddr) -> u8 {
+    get_ifa_netmask_prefix_len(ifa, addr).unwrap_or(match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    })
+}
+
+/// Retrieves the length of the network prefix of an interface address from its
+/// netmask, the netmask is read as belonging to the family of the address
+unsafe fn get_ifa_netmask_prefix_len(ifa: *mut *mut ifaddrs, addr: &IpAddr) -> Option<u8> {
+    let netmask = (*(*ifa)).ifa_netmask;
+
+    if netmask.is_null() {
+        return None;
+    }
+
+    match addr {
+        IpAddr::V4(_) => {
+            let in_addr = (*(netmask as *mut sockaddr_in)).sin_addr;
+            Some(u32::from_be(in_addr.s_addr).leading_ones() as u8)
+        }
+        IpAddr::V6(_) => {
+            let in6_addr = (*(netmask as *mut sockaddr_in6)).sin6_addr;
+            Some(u128::from_be_bytes(in6_addr.s6_addr).leading_ones() as u8)
+        }
+    }
+}
+
+/// Retrieves the operational flags of the interface holding an interface address.
+///
+/// `ifa_flags` is an `u64` on illumos and Solaris, the `IFF_*` flags read here
+/// fit in its lower 32 bits on every platform.
+unsafe fn get_ifa_flags(ifa: *mut *mut ifaddrs) -> InterfaceFlags {
+    let iflags = (*(*ifa)).ifa_flags as i32;
+    let mut flags = InterfaceFlags::empty();
+
+    flags.set(InterfaceFlags::UP, (iflags & IFF_UP) != 0);
+    flags.set(InterfaceFlags::RUNNING, (iflags & IFF_RUNNING) != 0);
+    flags.set(InterfaceFlags::BROADCAST, (iflags & IFF_BROADCAST) != 0);
+    flags.set(InterfaceFlags::MULTICAST, (iflags & IFF_MULTICAST) != 0);
+    flags.set(InterfaceFlags::POINTOPOINT, (iflags & IFF_POINTOPOINT) != 0);
+
+    flags
+}
+
 /// Determines if an interface address is a loopback address
 unsafe fn is_loopback_addr(ifa: *mut *mut ifaddrs) -> bool {
     let iflags = (*(*ifa)).ifa_flags as i32;
     (iflags & IFF_LOOPBACK) != 0
 }
+
+/// Determines if the interface holding an interface address is up and running
+unsafe fn is_up_addr(ifa: *mut *mut ifaddrs) -> bool {
+    let iflags = (*(*ifa)).ifa_flags as i32;
+    (iflags & (IFF_UP | IFF_RUNNING)) == (IFF_UP | IFF_RUNNING)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{mem, ptr};
+
+    use libc::{
+        ifaddrs, sa_family_t, sockaddr, sockaddr_in, AF_INET, AF_INET6, IFF_BROADCAST,
+        IFF_POINTOPOINT,
+    };
+
+    use super::{
+        get_ifa_addr, get_ifa_broadaddr, get_ifa_dest_addr, get_ifa_mac, get_sa_family,
+        walk_ifaddrs_list,
+    };
+    use crate::DestAddr;
+
+    #[test]
+    #[cfg(any(
+        target_os = "freebsd",
+        target_os = "openbsd",
+        target_os = "netbsd",
+        target_os = "dragonfly",
+        target_os = "macos",
+        target_os = "ios",
+    ))]
+    fn sa_family_is_one_byte_wide_on_bsd() {
+        assert_eq!(mem::size_of::<sa_family_t>(), 1);
+    }
+
+    #[test]
+    fn get_sa_family_reads_family() {
+        let mut addr: sockaddr = unsafe { mem::zeroed() };
+
+        addr.sa_family = AF_INET as sa_family_t;
+        assert_eq!(unsafe { get_sa_family(&addr) }, AF_INET);
+
+        addr.sa_family = AF_INET6 as sa_family_t;
+        assert_eq!(unsafe { get_sa_family(&addr) }, AF_INET6);
+    }
+
+    #[test]
+    fn walk_ifaddrs_list_with_null_head() {
+        let mut visited = 0;
+        let result = unsafe {
+            walk_ifaddrs_list(ptr::null_mut(), |_| {
+                visited += 1;
+                Ok(())
+            })
+        };
+
+        assert!(result.is_ok());
+        assert_eq!(visited, 0);
+    }
+
+    #[test]
+    fn walk_ifaddrs_list_with_single_node() {
+        let mut head: ifaddrs = unsafe { mem::zeroed() };
+        let mut addrs = Vec::new();
+
+        // The head node is the only node and holds no address
+        let result = unsafe {
+            walk_ifaddrs_list(&mut head, |ifa| {
+                addrs.push(get_ifa_addr(ifa));
+                Ok(())
+            })
+        };
+
+        assert!(result.is_ok());
+        assert_eq!(addrs, vec![None]);
+
+        let mut addr: sockaddr_in = unsafe { mem::zeroed() };
+        addr.sin_family = AF_INET as sa_family_t;
+        addr.sin_addr.s_addr = u32::from_be_bytes([192, 168, 1, 10]).to_be();
+        head.ifa_addr = &mut addr as *mut sockaddr_in as *mut sockaddr;
+        addrs.clear();
+
+        let result = unsafe {
+            walk_ifaddrs_list(&mut head, |ifa| {
+                addrs.push(get_ifa_addr(ifa));
+                Ok(())
+            })
+        };
+
+        assert!(result.is_ok());
+        assert_eq!(addrs, vec![Some("192.168.1.10".parse().unwrap())]);
+    }
+
+    #[test]
+    fn walk_ifaddrs_list_visits_the_last_node() {
+        let mut addr: sockaddr_in = unsafe { mem::zeroed() };
+        addr.sin_family = AF_INET as sa_family_t;
+        addr.sin_addr.s_addr = u32::from_be_bytes([10, 0, 0, 1]).to_be();
+
+        let mut tail: ifaddrs = unsafe { mem::zeroed() };
+        tail.ifa_addr = &mut addr as *mut sockaddr_in as *mut sockaddr;
+        let mut middle: ifaddrs = unsafe { mem::zeroed() };
+        middle.ifa_next = &mut tail;
+        let mut head: ifaddrs = unsafe { mem::zeroed() };
+        head.ifa_next = &mut middle;
+        let mut addrs = Vec::new();
+
+        // The tail node, whose `ifa_next` is null, must be visited as well
+        let result = unsafe {
+            walk_ifaddrs_list(&mut head, |ifa| {
+                addrs.push(get_ifa_addr(ifa));
+                Ok(())
+            })
+        };
+
+        assert!(result.is_ok());
+        assert_eq!(addrs, vec![None, None, Some("10.0.0.1".parse().unwrap())]);
+    }
+
+    #[test]
+    fn node_without_addresses_is_skipped() {
+        // Some tunnel or bonding interfaces are listed without any address
+        let mut node: ifaddrs = unsafe { mem::zeroed() };
+        node.ifa_flags = IFF_BROADCAST as _;
+        let mut ifa: *mut ifaddrs = &mut node;
+
+        unsafe {
+            assert_eq!(get_ifa_addr(&mut ifa), None);
+            assert_eq!(get_ifa_mac(&mut ifa), None);
+            assert_eq!(get_ifa_broadaddr(&mut ifa), None);
+            assert_eq!(get_ifa_dest_addr(&mut ifa), None);
+        }
+    }
+
+    #[test]
+    fn dest_addr_depends_on_interface_flags() {
+        let mut dstaddr: sockaddr_in = unsafe { mem::zeroed() };
+        dstaddr.sin_family = AF_INET as sa_family_t;
+        dstaddr.sin_addr.s_addr = u32::from_be_bytes([10, 8, 0, 1]).to_be();
+
+        let mut node: ifaddrs = unsafe { mem::zeroed() };
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            node.ifa_ifu = &mut dstaddr as *mut sockaddr_in as *mut sockaddr;
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        {
+            node.ifa_dstaddr = &mut dstaddr as *mut sockaddr_in as *mut sockaddr;
+        }
+        let mut ifa: *mut ifaddrs = &mut node;
+        let addr = "10.8.0.1".parse().unwrap();
+
+        unsafe {
+            assert_eq!(get_ifa_dest_addr(&mut ifa), None);
+
+            (*ifa).ifa_flags = IFF_BROADCAST as _;
+            assert_eq!(get_ifa_dest_addr(&mut ifa), Some(DestAddr::Broadcast(addr)));
+
+            (*ifa).ifa_flags = IFF_POINTOPOINT as _;
+            assert_eq!(
+                get_ifa_dest_addr(&mut ifa),
+                Some(DestAddr::PointToPoint(addr))
+            );
+            assert_eq!(get_ifa_broadaddr(&mut ifa), None);
+        }
+    }
+}
+
+/// AIX provides `getifaddrs` since AIX 7.2 but `libc` doesn't expose bindings
+/// for it, the declarations below mirror `<ifaddrs.h>`.
+#[cfg(target_os = "aix")]
+mod aix {
+    use libc::{c_char, c_int, c_uint, c_void, sockaddr};
+
+    #[repr(C)]
+    pub struct ifaddrs {
+        pub ifa_next: *mut ifaddrs,
+        pub ifa_name: *mut c_char,
+        pub ifa_flags: c_uint,
+        pub ifa_addr: *mut sockaddr,
+        pub ifa_netmask: *mut sockaddr,
+        pub ifa_dstaddr: *mut sockaddr,
+        pub ifa_data: *mut c_void,
+    }
+
+    extern "C" {
+        pub fn getifaddrs(ifap: *mut *mut ifaddrs) -> c_int;
+        pub fn freeifaddrs(ifa: *mut ifaddrs);
+    }
+}
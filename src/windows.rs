@@ -1,6 +1,8 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
 use std::{
     alloc::{alloc, dealloc, Layout},
-    net::IpAddr,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
     ptr::{NonNull, self},
     slice,
     marker::PhantomData,
@@ -11,13 +13,20 @@ use std::{
 use windows_sys::Win32::{
     Foundation::{
         GetLastError, LocalFree, BOOL, ERROR_ADDRESS_NOT_ASSOCIATED, ERROR_BUFFER_OVERFLOW,
-        ERROR_INSUFFICIENT_BUFFER, ERROR_INVALID_PARAMETER, ERROR_NOT_ENOUGH_MEMORY,
-        ERROR_NOT_SUPPORTED, ERROR_NO_DATA, ERROR_SUCCESS, WIN32_ERROR,
+        ERROR_INSUFFICIENT_BUFFER, ERROR_NOT_ENOUGH_MEMORY, ERROR_NOT_FOUND, ERROR_NOT_SUPPORTED,
+        ERROR_NO_DATA, ERROR_SUCCESS, WIN32_ERROR,
     },
     NetworkManagement::IpHelper::{
-        GetAdaptersAddresses, GetIpForwardTable, GET_ADAPTERS_ADDRESSES_FLAGS,
-        IP_ADAPTER_ADDRESSES_LH, IP_ADAPTER_UNICAST_ADDRESS_LH, MIB_IPFORWARDTABLE,
+        FreeMibTable, GetAdaptersAddresses, GetIpForwardTable, GetIpForwardTable2,
+        GetIpInterfaceEntry, InitializeIpInterfaceEntry, GAA_FLAG_INCLUDE_GATEWAYS,
+        GAA_FLAG_SKIP_UNICAST, GET_ADAPTERS_ADDRESSES_FLAGS, IF_TYPE_ETHERNET_CSMACD,
+        IF_TYPE_IEEE80211, IF_TYPE_PPP, IF_TYPE_SOFTWARE_LOOPBACK, IF_TYPE_TUNNEL,
+        IP_ADAPTER_ADDRESSES_LH, IP_ADAPTER_ADDRESS_DNS_ELIGIBLE, IP_ADAPTER_ADDRESS_TRANSIENT,
+        IP_ADAPTER_DHCP_ENABLED, IP_ADAPTER_DNS_SERVER_ADDRESS_XP, IP_ADAPTER_GATEWAY_ADDRESS_LH,
+        IP_ADAPTER_NO_MULTICAST, IP_ADAPTER_UNICAST_ADDRESS_LH, MIB_IPFORWARDTABLE,
+        MIB_IPFORWARD_TABLE2, MIB_IPINTERFACE_ROW,
     },
+    NetworkManagement::Ndis::IfOperStatusUp,
     Networking::WinSock::{
         ADDRESS_FAMILY, AF_INET, AF_INET6, AF_UNSPEC, SOCKADDR_IN, SOCKADDR_IN6, SOCKADDR,
     },
@@ -27,62 +36,297 @@ use windows_sys::Win32::{
 };
 
 use crate::error::Error;
+use crate::interface::mac_from_bytes;
+use crate::{AddrFamily, DestAddr, InterfaceAddr, InterfaceFlags, InterfaceKind, NetworkInterface};
 
-/// Retrieves the local ip addresses for this system.
-pub(crate) fn list_local_ip_addresses(family: ADDRESS_FAMILY) -> Result<Vec<IpAddr>, Error> {
-    /// An IPv4 address of 0.0.0.0 in the dwForwardDest member of the MIB_IPFORWARDROW structure is considered a
-    /// default route.
-    const DEFAULT_ROUTE: u32 = 0;
+/// Options to tune the Win32 calls performed to gather network interfaces
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowsOptions {
+    /// Initial size in bytes of the buffer handed to `GetAdaptersAddresses`.
+    ///
+    /// `GetAdaptersAddresses` reports `ERROR_BUFFER_OVERFLOW` and the call is
+    /// retried with the size it requests when the buffer is too small. When
+    /// `None` or zero the recommended size of 15KB is used.
+    pub buffer_size_hint: Option<u32>,
+    /// Flags handed to `GetAdaptersAddresses`, a combination of the
+    /// `GAA_FLAG_*` constants, zero by default.
+    ///
+    /// Skipping data which isn't reported anyway, such as DNS server or
+    /// multicast addresses (`GAA_FLAG_SKIP_DNS_SERVER | GAA_FLAG_SKIP_MULTICAST`),
+    /// speeds the call up on hosts with many adapters. Unicast addresses are
+    /// always requested, `GAA_FLAG_SKIP_UNICAST` is ignored.
+    pub adapter_addresses_flags: u32,
+}
 
-    // There can be multiple default routes (e.g. wifi and ethernet).
-    let default_route_interface_indices: Vec<u32> = {
-        let ip_forward_table = get_ip_forward_table(0).map_err(|error| match error {
-            ERROR_NO_DATA | ERROR_NOT_SUPPORTED => Error::LocalIpAddressNotFound,
-            error_code => Error::StrategyError(format_error_code(error_code)),
-        })?;
-
-        let table = unsafe {
-            slice::from_raw_parts(
-                ip_forward_table.table.as_ptr(),
-                ip_forward_table.dwNumEntries.try_into().unwrap(),
-            )
-        };
+/// Retrieves the local ip addresses for this system, held by the adapters with
+/// a default route, paired with the friendly name of the adapter holding them.
+/// Loopback addresses are never retrieved.
+///
+/// Addresses of the adapter with the lowest default route metric come first.
+pub(crate) fn list_local_ip_addresses(
+    family: ADDRESS_FAMILY,
+) -> Result<Vec<(String, IpAddr)>, Error> {
+    let mut local_ip_address = list_default_route_addresses(family)?;
 
-        table
-            .iter()
-            .filter_map(|row| {
-                if row.dwForwardDest == DEFAULT_ROUTE {
-                    Some(row.dwForwardIfIndex)
-                } else {
-                    None
-                }
-            })
-            .collect()
-    };
+    // Adapters with a lower metric take precedence and, within an adapter,
+    // primary addresses take precedence over secondary ones. The sort is stable
+    // so the order reported by the OS is kept otherwise.
+    local_ip_address.sort_by_key(|address| (address.metric, !address.is_primary));
+
+    Ok(local_ip_address
+        .into_iter()
+        .map(|address| (address.name, address.ip_address))
+        .collect())
+}
+
+/// Retrieves the IPv4 addresses held by every adapter with an IPv4 default route,
+/// there can be multiple default routes (e.g. wifi and ethernet).
+///
+/// Addresses are sorted by the metric (`dwForwardMetric1`) of the default route
+/// of the adapter holding them, lowest first, so callers can apply their own
+/// preference. Addresses of adapters with the same metric keep the order
+/// reported by `GetAdaptersAddresses`. Loopback addresses are never retrieved.
+pub fn list_default_route_ip_addresses() -> Result<Vec<IpAddr>, Error> {
+    let mut addresses = list_default_route_addresses(AF_INET)?;
+
+    addresses.sort_by_key(|address| address.metric);
+
+    Ok(addresses
+        .into_iter()
+        .map(|address| address.ip_address)
+        .collect())
+}
+
+/// Address held by an adapter with an IPv4 default route
+struct DefaultRouteAddress {
+    /// Friendly name of the adapter
+    name: String,
+    ip_address: IpAddr,
+    is_primary: bool,
+    /// Lowest metric among the default routes of the adapter
+    metric: u32,
+}
+
+/// Retrieves the addresses held by the adapters with an IPv4 default route in
+/// the order reported by `GetAdaptersAddresses`, loopback addresses are skipped.
+fn list_default_route_addresses(family: ADDRESS_FAMILY) -> Result<Vec<DefaultRouteAddress>, Error> {
+    // There can be multiple default routes (e.g. wifi and ethernet).
+    let default_routes = default_routes()?;
 
     let adapter_addresses = get_adapter_addresses(family, 0).map_err(|error| match error {
         ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA => Error::LocalIpAddressNotFound,
-        error_code => Error::StrategyError(format_error_code(error_code)),
+        error_code => win32_error(error_code),
     })?;
     let adapter_addresses_iter = LinkedListIter::new(Some(adapter_addresses.ptr));
 
-    let local_ip_address = adapter_addresses_iter
-        .filter(|adapter_address| {
+    Ok(adapter_addresses_iter
+        .filter_map(|adapter_address| {
             let interface_index = unsafe { adapter_address.Anonymous1.Anonymous.IfIndex };
-            default_route_interface_indices.contains(&interface_index)
+            let metric = default_routes
+                .iter()
+                .filter(|(index, _)| *index == interface_index)
+                .map(|(_, metric)| *metric)
+                .min()?;
+
+            Some((adapter_address, metric))
         })
-        .flat_map(|default_adapter_address| {
+        .flat_map(|(default_adapter_address, metric)| {
+            let name = get_friendly_name(default_adapter_address);
             let unicast_addresses_iter =
                 LinkedListIter::new(NonNull::new(default_adapter_address.FirstUnicastAddress));
 
-            unicast_addresses_iter.filter_map(|unicast_address| {
+            unicast_addresses_iter.filter_map(move |unicast_address| {
                 let socket_address = NonNull::new(unicast_address.Address.lpSockaddr)?;
                 get_ip_address_from_socket_address(socket_address)
+                    // an odd routing table may hold a default route through
+                    // the loopback adapter
+                    .filter(|ip_address| !ip_address.is_loopback())
+                    .map(|ip_address| DefaultRouteAddress {
+                        name: name.clone(),
+                        ip_address,
+                        is_primary: is_primary_unicast_address(unicast_address),
+                        metric,
+                    })
             })
         })
+        .collect())
+}
+
+/// Retrieves the interface indices of the adapters holding an IPv4 default route,
+/// there can be multiple default routes (e.g. wifi and ethernet).
+///
+/// Indices keep the order of the IPv4 routing table returned by `GetIpForwardTable`,
+/// an adapter holding several default routes is listed once per route.
+pub fn default_route_interfaces() -> Result<Vec<u32>, Error> {
+    Ok(default_routes()?
+        .into_iter()
+        .map(|(interface_index, _)| interface_index)
+        .collect())
+}
+
+/// Retrieves the interface index and the metric (`dwForwardMetric1`) of each
+/// IPv4 default route, in the order of the IPv4 routing table returned by
+/// `GetIpForwardTable`.
+fn default_routes() -> Result<Vec<(u32, u32)>, Error> {
+    /// An IPv4 address of 0.0.0.0 in the dwForwardDest member of the MIB_IPFORWARDROW structure is considered a
+    /// default route.
+    const DEFAULT_ROUTE: u32 = 0;
+
+    let ip_forward_table = get_ip_forward_table(0).map_err(|error| match error {
+        ERROR_NO_DATA | ERROR_NOT_SUPPORTED => Error::LocalIpAddressNotFound,
+        error_code => win32_error(error_code),
+    })?;
+
+    let len = usize::try_from(ip_forward_table.dwNumEntries).map_err(|_| {
+        Error::StrategyError(String::from(
+            "The IPv4 routing table length overflows usize",
+        ))
+    })?;
+    let table = unsafe { slice::from_raw_parts(ip_forward_table.table.as_ptr(), len) };
+
+    Ok(table
+        .iter()
+        .filter_map(|row| {
+            if row.dwForwardDest == DEFAULT_ROUTE {
+                Some((row.dwForwardIfIndex, row.dwForwardMetric1))
+            } else {
+                None
+            }
+        })
+        .collect())
+}
+
+/// Retrieves the IPv4 address of the default gateway for this system
+///
+/// The IPv4 routing table is read through `GetIpForwardTable2` just as
+/// [`default_gateway_v6`] does, the gateway of the default route with the lowest
+/// metric is returned.
+pub fn default_gateway() -> Result<IpAddr, Error> {
+    default_gateway_impl(AF_INET)
+}
+
+/// Retrieves the IPv6 address of the default gateway for this system
+///
+/// The IPv6 routing table is read through `GetIpForwardTable2`, the `GetIpForwardTable`
+/// function used to find the local IP address only covers IPv4 routes.
+/// `GetIpForwardTable2` is available since Windows Vista. When multiple default routes
+/// are available the gateway of the one with the lowest metric is returned.
+pub fn default_gateway_v6() -> Result<IpAddr, Error> {
+    default_gateway_impl(AF_INET6)
+}
+
+fn default_gateway_impl(family: ADDRESS_FAMILY) -> Result<IpAddr, Error> {
+    let ip_forward_table = get_ip_forward_table2(family).map_err(|error| match error {
+        ERROR_NO_DATA | ERROR_NOT_FOUND | ERROR_NOT_SUPPORTED => Error::LocalIpAddressNotFound,
+        error_code => win32_error(error_code),
+    })?;
+
+    let len = usize::try_from(ip_forward_table.NumEntries).map_err(|_| {
+        Error::StrategyError(String::from("The IP routing table length overflows usize"))
+    })?;
+    let table = unsafe { slice::from_raw_parts(ip_forward_table.Table.as_ptr(), len) };
+
+    table
+        .iter()
+        .filter(|row| row.DestinationPrefix.PrefixLength == 0)
+        .filter_map(|row| {
+            let next_hop = NonNull::from(&row.NextHop).cast::<SOCKADDR>();
+            get_ip_address_from_socket_address(next_hop).map(|ip_address| (row.Metric, ip_address))
+        })
+        .filter(|(_, ip_address)| !ip_address.is_unspecified())
+        .min_by_key(|(metric, _)| *metric)
+        .map(|(_, ip_address)| ip_address)
+        .ok_or(Error::LocalIpAddressNotFound)
+}
+
+/// Retrieves the local IP address of the family, `AF_INET` or `AF_INET6`, whose
+/// best default route has the lowest metric.
+///
+/// Default routes are read through `GetIpForwardTable2` and compared by the sum
+/// of the route metric and the metric of the interface holding them (as read
+/// through `GetIpInterfaceEntry`), which is how Windows ranks routes. IPv6 is
+/// preferred when both have the same metric. This heuristic may not match the
+/// family used by new outbound connections, which is chosen by the RFC 6724
+/// destination address selection of `getaddrinfo` regardless of route metrics.
+/// The first address of the same family held by the adapter of the best default
+/// route is returned, link-local addresses are skipped.
+pub fn best_local_ip() -> Result<IpAddr, Error> {
+    let ip_forward_table = get_ip_forward_table2(AF_UNSPEC).map_err(|error| match error {
+        ERROR_NO_DATA | ERROR_NOT_FOUND | ERROR_NOT_SUPPORTED => Error::LocalIpAddressNotFound,
+        error_code => win32_error(error_code),
+    })?;
+
+    let len = usize::try_from(ip_forward_table.NumEntries).map_err(|_| {
+        Error::StrategyError(String::from("The IP routing table length overflows usize"))
+    })?;
+    let table = unsafe { slice::from_raw_parts(ip_forward_table.Table.as_ptr(), len) };
+
+    let default_routes: Vec<(u32, Option<u32>, bool, u32)> = table
+        .iter()
+        .filter(|row| row.DestinationPrefix.PrefixLength == 0)
+        .map(|row| {
+            let family = unsafe { row.DestinationPrefix.Prefix.si_family };
+            let interface_metric = get_interface_metric(family, row.InterfaceIndex);
+            (
+                row.Metric,
+                interface_metric,
+                family != AF_INET6,
+                row.InterfaceIndex,
+            )
+        })
         .collect();
 
-    Ok(local_ip_address)
+    for (is_ipv4, index) in rank_default_routes(default_routes) {
+        let ip_address = addresses_for_index(index)?.into_iter().find(|ip_address| {
+            ip_address.is_ipv4() == is_ipv4 && !crate::is_link_local(ip_address)
+        });
+
+        if let Some(ip_address) = ip_address {
+            return Ok(ip_address);
+        }
+    }
+
+    Err(Error::LocalIpAddressNotFound)
+}
+
+/// Orders default routes, given as their route metric, the metric of their
+/// interface, whether they are IPv4 routes and the index of their interface, from
+/// the most to the least preferred one.
+///
+/// Routes are ranked by the sum of both metrics, routes whose interface metric
+/// is unknown are ranked last. IPv6 routes take precedence on ties.
+fn rank_default_routes(routes: Vec<(u32, Option<u32>, bool, u32)>) -> Vec<(bool, u32)> {
+    let mut routes: Vec<(u32, bool, u32)> = routes
+        .into_iter()
+        .map(|(route_metric, interface_metric, is_ipv4, index)| {
+            let metric = interface_metric.map_or(u32::MAX, |interface_metric| {
+                route_metric.saturating_add(interface_metric)
+            });
+            (metric, is_ipv4, index)
+        })
+        .collect();
+    routes.sort_unstable();
+
+    routes
+        .into_iter()
+        .map(|(_, is_ipv4, index)| (is_ipv4, index))
+        .collect()
+}
+
+/// Retrieves the metric of the interface with the given index for the given
+/// family through `GetIpInterfaceEntry`, `None` if the family is not enabled on
+/// the interface.
+fn get_interface_metric(family: ADDRESS_FAMILY, index: u32) -> Option<u32> {
+    let mut row: MIB_IPINTERFACE_ROW = unsafe { mem::zeroed() };
+
+    unsafe { InitializeIpInterfaceEntry(&mut row) };
+    row.Family = family;
+    row.InterfaceIndex = index;
+
+    match unsafe { GetIpInterfaceEntry(&mut row) } {
+        ERROR_SUCCESS => Some(row.Metric),
+        _ => None,
+    }
 }
 
 /// Perform a search over the system's network interfaces using `GetAdaptersAddresses`,
@@ -105,8 +349,112 @@ pub(crate) fn list_local_ip_addresses(family: ADDRESS_FAMILY) -> Result<Vec<IpAd
 /// }
 /// ```
 pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
-    let adapter_addresses = get_adapter_addresses(AF_UNSPEC, 0)
-        .map_err(|error_code| Error::StrategyError(format_error_code(error_code)))?;
+    let ifas = list_afinet_netifas_info()?;
+
+    Ok(crate::dedup_netifas(ifas.into_iter().map(|ifa| (ifa.name, ifa.addr))).collect())
+}
+
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas`] does, yielding the addresses lazily.
+///
+/// The buffer filled by `GetAdaptersAddresses` is walked as the iterator is
+/// advanced and released once the iterator is dropped. Unlike
+/// [`list_afinet_netifas`], exact `(name, ip)` duplicates the system reports are
+/// yielded as they are, so nothing is allocated besides the names.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::iter_afinet_netifas;
+///
+/// let first = iter_afinet_netifas().unwrap().find(|(_, ip)| ip.is_ipv4());
+///
+/// println!("First IPv4 address: {:?}", first);
+/// ```
+pub fn iter_afinet_netifas() -> Result<impl Iterator<Item = (String, IpAddr)>, Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_UNSPEC, 0) {
+        Ok(adapter_addresses) => Some(adapter_addresses),
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => None,
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
+    let adapter = adapter_addresses
+        .as_ref()
+        .map(|adapter_addresses| adapter_addresses.ptr);
+
+    Ok(AfinetNetifasIter {
+        unicast: adapter
+            .and_then(|adapter| NonNull::new(unsafe { adapter.as_ref() }.FirstUnicastAddress)),
+        adapter,
+        _adapter_addresses: adapter_addresses,
+    })
+}
+
+/// Iterator over the unicast addresses of the adapters listed by
+/// `GetAdaptersAddresses`, refer to [`iter_afinet_netifas`]
+struct AfinetNetifasIter {
+    adapter: Option<NonNull<IP_ADAPTER_ADDRESSES_LH>>,
+    unicast: Option<NonNull<IP_ADAPTER_UNICAST_ADDRESS_LH>>,
+    // keeps the nodes pointed by `adapter` and `unicast` alive
+    _adapter_addresses: Option<ReadonlyResource<IP_ADAPTER_ADDRESSES_LH>>,
+}
+
+impl Iterator for AfinetNetifasIter {
+    type Item = (String, IpAddr);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let adapter_address = unsafe { self.adapter?.as_ref() };
+
+            match self.unicast {
+                Some(unicast_address) => {
+                    let unicast_address = unsafe { unicast_address.as_ref() };
+                    self.unicast = LinkedListIterator::next(unicast_address);
+
+                    let ip_address = NonNull::new(unicast_address.Address.lpSockaddr)
+                        .and_then(get_ip_address_from_socket_address);
+
+                    if let Some(ip_address) = ip_address {
+                        return Some((get_friendly_name(adapter_address), ip_address));
+                    }
+                }
+                None => {
+                    self.adapter = LinkedListIterator::next(adapter_address);
+                    self.unicast = self.adapter.and_then(|adapter| {
+                        NonNull::new(unsafe { adapter.as_ref() }.FirstUnicastAddress)
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Perform a search over the system's network interfaces using `GetAdaptersAddresses`,
+/// each address belonging to the `AF_INET` or `AF_INET6` family is retrieved
+/// along with its prefix length and the index, MAC address and MTU of its adapter.
+///
+/// The prefix length is read from `OnLinkPrefixLength`. The index is the IPv4
+/// interface index (`IfIndex`) of the adapter for IPv4 addresses and its IPv6
+/// interface index (`Ipv6IfIndex`) for IPv6 addresses. The MAC address is read
+/// from `PhysicalAddress` and the MTU from `Mtu`, the loopback adapter reports
+/// an MTU of `u32::MAX` which is not retrieved. The flags are built from
+/// `OperStatus`, `IfType` and `Flags`, refer to [`InterfaceFlags`]. The scope id
+/// of link-local IPv6 addresses is read from `sin6_scope_id`.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_network_interfaces;
+///
+/// for ifa in list_network_interfaces().unwrap() {
+///     println!("{} ({:?}):\t{:?}/{:?}", ifa.name, ifa.index, ifa.addr, ifa.prefix_len);
+/// }
+/// ```
+pub fn list_network_interfaces() -> Result<Vec<NetworkInterface>, Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_UNSPEC, 0) {
+        Ok(adapter_addresses) => adapter_addresses,
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => return Ok(Vec::new()),
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
     let adapter_addresses_iter = LinkedListIter::new(Some(adapter_addresses.ptr));
 
     let network_interfaces = adapter_addresses_iter
@@ -114,55 +462,730 @@ pub fn list_afinet_netifas() -> Result<Vec<(String, IpAddr)>, Error> {
             let unicast_addresses_iter =
                 LinkedListIter::new(NonNull::new(adapter_address.FirstUnicastAddress));
 
-            let friendly_name = unsafe {
-                #[allow(unused_unsafe)]
-                // SAFETY: This is basically how `wcslen` works under the hood. `wcslen` is unsafe because the pointer
-                // is not checked for null and if there is no null-terminating character, it will run forever.
-                // Therefore, safety relies on the operating sysytem always returning a valid string.
-                let len = unsafe {
-                    let mut ptr = adapter_address.FriendlyName;
-                    while *ptr != 0 {
-                        ptr = ptr.offset(1);
+            let friendly_name = get_friendly_name(adapter_address);
+            let mac = get_adapter_mac(adapter_address);
+            let is_loopback = adapter_address.IfType == IF_TYPE_SOFTWARE_LOOPBACK;
+            let mtu = Some(adapter_address.Mtu)
+                .filter(|mtu| !is_loopback && *mtu != 0 && *mtu != u32::MAX);
+            let flags = get_adapter_flags(adapter_address);
+            let kind = get_adapter_kind(adapter_address);
+
+            unicast_addresses_iter.filter_map(move |unicast_address| {
+                let socket_address = NonNull::new(unicast_address.Address.lpSockaddr)?;
+                get_ip_address_from_socket_address(socket_address).map(|ip_address| {
+                    let index = match ip_address {
+                        IpAddr::V4(_) => unsafe { adapter_address.Anonymous1.Anonymous.IfIndex },
+                        IpAddr::V6(_) => adapter_address.Ipv6IfIndex,
+                    };
+
+                    NetworkInterface {
+                        name: friendly_name.clone(),
+                        addr: ip_address,
+                        prefix_len: Some(unicast_address.OnLinkPrefixLength),
+                        is_loopback,
+                        // a zero index means the family is not enabled on the adapter
+                        index: Some(index).filter(|index| *index != 0),
+                        mac,
+                        mtu,
+                        flags,
+                        kind,
+                        scope_id: if crate::is_link_local(&ip_address) {
+                            get_scope_id(socket_address).or(Some(index).filter(|index| *index != 0))
+                        } else {
+                            None
+                        },
+                        // Windows doesn't report broadcast nor peer addresses
+                        dest_addr: match ip_address {
+                            IpAddr::V4(ip_address) if flags.contains(InterfaceFlags::BROADCAST) => {
+                                Some(DestAddr::Broadcast(IpAddr::V4(broadcast_address(
+                                    ip_address,
+                                    unicast_address.OnLinkPrefixLength,
+                                ))))
+                            }
+                            _ => None,
+                        },
+                        valid_lifetime: Some(unicast_address.ValidLifetime),
+                        preferred_lifetime: Some(unicast_address.PreferredLifetime),
                     }
-                    ptr.offset_from(adapter_address.FriendlyName)
-                        .try_into()
-                        .unwrap()
-                };
+                })
+            })
+        })
+        .collect();
+
+    Ok(network_interfaces)
+}
+
+/// Perform a search over the system's network interfaces using `GetAdaptersAddresses`
+/// just as [`list_afinet_netifas`] does, each address is paired with a flag which is
+/// `true` when the address is the primary address of the adapter.
+///
+/// Windows doesn't flag primary addresses explicitly, an address is considered primary
+/// when it's eligible to be registered in DNS (`IP_ADAPTER_ADDRESS_DNS_ELIGIBLE`) and
+/// is not transient (`IP_ADAPTER_ADDRESS_TRANSIENT`).
+pub fn list_afinet_netifas_with_primary() -> Result<Vec<(String, IpAddr, bool)>, Error> {
+    let ifas = list_afinet_netifas_info()?;
+
+    Ok(ifas
+        .into_iter()
+        .map(|ifa| (ifa.name, ifa.addr, ifa.is_primary))
+        .collect())
+}
+
+/// Perform a search over the system's network interfaces using `GetAdaptersAddresses`,
+/// retrieved network interfaces belonging to both socket address families
+/// `AF_INET` and `AF_INET6` are retrieved along with details on the adapter
+/// holding them.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_afinet_netifas_info;
+///
+/// let ifas = list_afinet_netifas_info().unwrap();
+///
+/// for ifa in ifas.iter().filter(|ifa| !ifa.is_loopback) {
+///     println!("{}:\t{:?}", ifa.name, ifa.addr);
+/// }
+/// ```
+pub fn list_afinet_netifas_info() -> Result<Vec<InterfaceAddr>, Error> {
+    list_afinet_netifas_info_with_options(&WindowsOptions::default())
+}
+
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas_info`] does, tuning the `GetAdaptersAddresses` call
+/// with the given options.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::{list_afinet_netifas_info_with_options, WindowsOptions};
+///
+/// // Hosts with hundreds of adapters overflow the default buffer size
+/// let options = WindowsOptions {
+///     buffer_size_hint: Some(256 * 1024),
+///     ..WindowsOptions::default()
+/// };
+/// let ifas = list_afinet_netifas_info_with_options(&options).unwrap();
+///
+/// for ifa in ifas.iter() {
+///     println!("{}:\t{:?}", ifa.name, ifa.addr);
+/// }
+/// ```
+pub fn list_afinet_netifas_info_with_options(
+    options: &WindowsOptions,
+) -> Result<Vec<InterfaceAddr>, Error> {
+    list_afinet_netifas_info_impl(AF_UNSPEC, options)
+}
+
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas`] does, only retrieving addresses of the given `family`.
+///
+/// The family is given to `GetAdaptersAddresses`, so addresses of other
+/// families are filtered out by the system rather than being enumerated.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::{list_afinet_netifas_family, AddrFamily};
+///
+/// for (name, ip) in list_afinet_netifas_family(AddrFamily::Inet).unwrap() {
+///     println!("{}:\t{:?}", name, ip);
+/// }
+/// ```
+pub fn list_afinet_netifas_family(family: AddrFamily) -> Result<Vec<(String, IpAddr)>, Error> {
+    let family = match family {
+        AddrFamily::Inet => AF_INET,
+        AddrFamily::Inet6 => AF_INET6,
+        AddrFamily::Unspec => AF_UNSPEC,
+    };
+    let ifas = list_afinet_netifas_info_impl(family, &WindowsOptions::default())?;
+
+    Ok(ifas.into_iter().map(|ifa| (ifa.name, ifa.addr)).collect())
+}
+
+fn list_afinet_netifas_info_impl(
+    family: ADDRESS_FAMILY,
+    options: &WindowsOptions,
+) -> Result<Vec<InterfaceAddr>, Error> {
+    let flags = options.adapter_addresses_flags & !GAA_FLAG_SKIP_UNICAST;
+    let adapter_addresses =
+        match get_adapter_addresses_with_size_hint(family, flags, options.buffer_size_hint) {
+            Ok(adapter_addresses) => adapter_addresses,
+            // No adapter holds an address, as in a machine with every adapter disabled
+            Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => return Ok(Vec::new()),
+            Err(error_code) => return Err(win32_error(error_code)),
+        };
+    let adapter_addresses_iter = LinkedListIter::new(Some(adapter_addresses.ptr));
+
+    let network_interfaces = adapter_addresses_iter
+        .flat_map(|adapter_address| {
+            let unicast_addresses_iter =
+                LinkedListIter::new(NonNull::new(adapter_address.FirstUnicastAddress));
+
+            let friendly_name = get_friendly_name(adapter_address);
+
+            unicast_addresses_iter.filter_map(move |unicast_address| {
+                let socket_address = NonNull::new(unicast_address.Address.lpSockaddr)?;
+                get_ip_address_from_socket_address(socket_address).map(|ip_address| InterfaceAddr {
+                    name: friendly_name.clone(),
+                    addr: ip_address,
+                    is_loopback: adapter_address.IfType == IF_TYPE_SOFTWARE_LOOPBACK,
+                    is_primary: is_primary_unicast_address(unicast_address),
+                    is_up: adapter_address.OperStatus == IfOperStatusUp,
+                    is_multicast: is_multicast_adapter(adapter_address),
+                    prefix_len: unicast_address.OnLinkPrefixLength,
+                    kind: get_adapter_kind(adapter_address),
+                })
+            })
+        })
+        .collect();
+
+    Ok(network_interfaces)
+}
+
+/// Retrieves the broadcast IPv4 address of the adapter with the given friendly
+/// name, computed from its first IPv4 unicast address and the length of its
+/// on-link prefix as Windows doesn't report broadcast addresses. The friendly
+/// name is compared case-insensitively.
+///
+/// Returns `Error::InterfaceNotFound` if no adapter with such name exists, and
+/// `Error::LocalIpAddressNotFound` if the adapter holds no IPv4 address.
+pub fn broadcast_ip_on(name: &str) -> Result<IpAddr, Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_UNSPEC, 0) {
+        Ok(adapter_addresses) => adapter_addresses,
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => {
+            return Err(Error::InterfaceNotFound(String::from(name)))
+        }
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
+
+    let adapter_address = LinkedListIter::new(Some(adapter_addresses.ptr))
+        .find(|adapter_address| {
+            crate::same_interface_name(&get_friendly_name(adapter_address), name)
+        })
+        .ok_or_else(|| Error::InterfaceNotFound(String::from(name)))?;
+
+    LinkedListIter::new(NonNull::new(adapter_address.FirstUnicastAddress))
+        .find_map(|unicast_address| {
+            let socket_address = NonNull::new(unicast_address.Address.lpSockaddr)?;
+            match get_ip_address_from_socket_address(socket_address)? {
+                IpAddr::V4(ip_address) => Some(IpAddr::V4(broadcast_address(
+                    ip_address,
+                    unicast_address.OnLinkPrefixLength,
+                ))),
+                IpAddr::V6(_) => None,
+            }
+        })
+        .ok_or(Error::LocalIpAddressNotFound)
+}
+
+/// Retrieves the addresses of the DNS servers configured on the adapters which
+/// are up, read from the `FirstDnsServerAddress` list of each adapter. Servers
+/// configured on several adapters are listed once, in the order adapters are
+/// reported by `GetAdaptersAddresses`.
+pub(crate) fn dns_server_addresses() -> Result<Vec<IpAddr>, Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_UNSPEC, 0) {
+        Ok(adapter_addresses) => adapter_addresses,
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => return Ok(Vec::new()),
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
+
+    let mut dns_servers: Vec<IpAddr> = Vec::new();
+
+    for adapter_address in LinkedListIter::new(Some(adapter_addresses.ptr)) {
+        if adapter_address.OperStatus != IfOperStatusUp {
+            continue;
+        }
+
+        let dns_server_addresses =
+            LinkedListIter::new(NonNull::new(adapter_address.FirstDnsServerAddress));
+
+        for dns_server_address in dns_server_addresses {
+            let ip_address = NonNull::new(dns_server_address.Address.lpSockaddr)
+                .and_then(get_ip_address_from_socket_address);
+
+            match ip_address {
+                Some(ip_address) if !dns_servers.contains(&ip_address) => {
+                    dns_servers.push(ip_address)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(dns_servers)
+}
+
+/// Retrieves the gateways of each adapter, read from the `FirstGatewayAddress`
+/// list of the adapter, paired with the adapter's friendly name. An adapter
+/// with several gateways (e.g. an IPv4 and an IPv6 one) is listed once per
+/// gateway, adapters without a gateway are not listed.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::adapter_gateways;
+///
+/// if let Ok(gateways) = adapter_gateways() {
+///     for (name, gateway) in gateways.iter() {
+///         println!("{}:\t{:?}", name, gateway);
+///     }
+/// }
+/// ```
+pub fn adapter_gateways() -> Result<Vec<(String, IpAddr)>, Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_UNSPEC, GAA_FLAG_INCLUDE_GATEWAYS) {
+        Ok(adapter_addresses) => adapter_addresses,
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => return Ok(Vec::new()),
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
 
-                slice::from_raw_parts(adapter_address.FriendlyName, len)
+    let mut gateways: Vec<(String, IpAddr)> = Vec::new();
+
+    for adapter_address in LinkedListIter::new(Some(adapter_addresses.ptr)) {
+        let gateway_addresses =
+            LinkedListIter::new(NonNull::new(adapter_address.FirstGatewayAddress));
+
+        for gateway_address in gateway_addresses {
+            if let Some(ip_address) = NonNull::new(gateway_address.Address.lpSockaddr)
+                .and_then(get_ip_address_from_socket_address)
+            {
+                gateways.push((get_friendly_name(adapter_address), ip_address));
+            }
+        }
+    }
+
+    Ok(gateways)
+}
+
+/// Retrieves the DHCPv4 server which leased the address of each adapter, read
+/// from `Dhcpv4Server`, paired with the adapter's friendly name.
+///
+/// The server is `None` for adapters with a static IPv4 configuration and for
+/// adapters using DHCP which haven't got a lease yet.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::adapter_dhcp_servers;
+///
+/// if let Ok(dhcp_servers) = adapter_dhcp_servers() {
+///     for (name, dhcp_server) in dhcp_servers.iter() {
+///         println!("{}:\t{:?}", name, dhcp_server);
+///     }
+/// }
+/// ```
+pub fn adapter_dhcp_servers() -> Result<Vec<(String, Option<IpAddr>)>, Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_UNSPEC, 0) {
+        Ok(adapter_addresses) => adapter_addresses,
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => return Ok(Vec::new()),
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
+
+    Ok(LinkedListIter::new(Some(adapter_addresses.ptr))
+        .map(|adapter_address| {
+            let flags = unsafe { adapter_address.Anonymous2.Flags };
+            let dhcp_server = if flags & IP_ADAPTER_DHCP_ENABLED != 0 {
+                NonNull::new(adapter_address.Dhcpv4Server.lpSockaddr)
+                    .and_then(get_ip_address_from_socket_address)
+            } else {
+                None
             };
 
+            (get_friendly_name(adapter_address), dhcp_server)
+        })
+        .collect())
+}
+
+/// Retrieves the broadcast IPv4 address of the adapter holding the local IPv4
+/// address returned by `local_ip`, computed from the address and the length of
+/// its on-link prefix as Windows doesn't report broadcast addresses.
+///
+/// Returns `Error::LocalIpAddressNotFound` if the local IPv4 address is held by
+/// a point-to-point adapter (PPP or tunnel), which has no broadcast address.
+pub fn local_broadcast_ip() -> Result<IpAddr, Error> {
+    let local_ip = list_local_ip_addresses(AF_INET)?
+        .into_iter()
+        .map(|(_, ip_address)| ip_address)
+        .find(|ip_address| ip_address.is_ipv4())
+        .ok_or(Error::LocalIpAddressNotFound)?;
+
+    let adapter_addresses = get_adapter_addresses(AF_INET, 0).map_err(|error| match error {
+        ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA => Error::LocalIpAddressNotFound,
+        error_code => win32_error(error_code),
+    })?;
+
+    LinkedListIter::new(Some(adapter_addresses.ptr))
+        .filter(|adapter_address| {
+            !get_adapter_flags(adapter_address).contains(InterfaceFlags::POINTOPOINT)
+        })
+        .flat_map(|adapter_address| {
+            LinkedListIter::new(NonNull::new(adapter_address.FirstUnicastAddress))
+        })
+        .find_map(|unicast_address| {
+            let socket_address = NonNull::new(unicast_address.Address.lpSockaddr)?;
+            match get_ip_address_from_socket_address(socket_address)? {
+                IpAddr::V4(ip_address) if IpAddr::V4(ip_address) == local_ip => Some(IpAddr::V4(
+                    broadcast_address(ip_address, unicast_address.OnLinkPrefixLength),
+                )),
+                _ => None,
+            }
+        })
+        .ok_or(Error::LocalIpAddressNotFound)
+}
+
+/// Computes the broadcast address of the network of an IPv4 address, that is
+/// the address with every bit outside of the network prefix set
+fn broadcast_address(ip_address: Ipv4Addr, prefix_len: u8) -> Ipv4Addr {
+    let host_mask = u32::MAX.checked_shr(u32::from(prefix_len)).unwrap_or(0);
+    Ipv4Addr::from(u32::from(ip_address) | host_mask)
+}
+
+/// Perform a search over the system's network interfaces just as
+/// [`list_afinet_netifas`] does, skipping malformed entries instead of failing.
+///
+/// Unicast addresses without a socket address or whose socket address belongs
+/// to an unexpected family are skipped, their count is returned along with the
+/// entries which were retrieved. A failing `GetAdaptersAddresses` call still
+/// fails the search.
+pub fn list_afinet_netifas_lenient() -> Result<(Vec<(String, IpAddr)>, usize), Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_UNSPEC, 0) {
+        Ok(adapter_addresses) => adapter_addresses,
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => return Ok((Vec::new(), 0)),
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
+
+    let mut network_interfaces = Vec::new();
+    let mut skipped = 0;
+
+    for adapter_address in LinkedListIter::new(Some(adapter_addresses.ptr)) {
+        let friendly_name = get_friendly_name(adapter_address);
+
+        for unicast_address in
+            LinkedListIter::new(NonNull::new(adapter_address.FirstUnicastAddress))
+        {
+            match NonNull::new(unicast_address.Address.lpSockaddr)
+                .and_then(get_ip_address_from_socket_address)
+            {
+                Some(ip_address) => network_interfaces.push((friendly_name.clone(), ip_address)),
+                None => skipped += 1,
+            }
+        }
+    }
+
+    Ok((crate::dedup_netifas(network_interfaces).collect(), skipped))
+}
+
+/// Retrieves the IPv6 addresses of the system's network adapters along with
+/// the flow information (`sin6_flowinfo`, traffic class and flow label) of
+/// their socket address, in host byte order.
+///
+/// Most adapters report a zero flow information.
+pub fn list_afinet_netifas_v6_flowinfo() -> Result<Vec<(String, Ipv6Addr, u32)>, Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_INET6, 0) {
+        Ok(adapter_addresses) => adapter_addresses,
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => return Ok(Vec::new()),
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
+    let adapter_addresses_iter = LinkedListIter::new(Some(adapter_addresses.ptr));
+
+    let network_interfaces = adapter_addresses_iter
+        .flat_map(|adapter_address| {
+            let unicast_addresses_iter =
+                LinkedListIter::new(NonNull::new(adapter_address.FirstUnicastAddress));
+
+            let friendly_name = get_friendly_name(adapter_address);
+
+            unicast_addresses_iter.filter_map(move |unicast_address| {
+                let socket_address = NonNull::new(unicast_address.Address.lpSockaddr)?;
+
+                if unsafe { socket_address.as_ref().sa_family } != AF_INET6 {
+                    return None;
+                }
+
+                let socket_address = unsafe { socket_address.cast::<SOCKADDR_IN6>().as_ref() };
+                let address = unsafe { socket_address.sin6_addr.u.Byte };
+
+                Some((
+                    friendly_name.clone(),
+                    Ipv6Addr::from(address),
+                    u32::from_be(socket_address.sin6_flowinfo),
+                ))
+            })
+        })
+        .collect();
+
+    Ok(network_interfaces)
+}
+
+/// Perform a search over the system's network adapters using `GetAdaptersAddresses`,
+/// each adapter is listed once along with all of its addresses belonging to both
+/// socket address families `AF_INET` and `AF_INET6`.
+///
+/// Adapters keep the order reported by `GetAdaptersAddresses`, adapters without
+/// addresses are listed with an empty `Vec`.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::list_afinet_netifas_by_adapter;
+///
+/// for (name, ip_addresses) in list_afinet_netifas_by_adapter().unwrap() {
+///     println!("{}:\t{:?}", name, ip_addresses);
+/// }
+/// ```
+pub fn list_afinet_netifas_by_adapter() -> Result<Vec<(String, Vec<IpAddr>)>, Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_UNSPEC, 0) {
+        Ok(adapter_addresses) => adapter_addresses,
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => return Ok(Vec::new()),
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
+    let adapter_addresses_iter = LinkedListIter::new(Some(adapter_addresses.ptr));
+
+    let adapters = adapter_addresses_iter
+        .map(|adapter_address| {
+            let unicast_addresses_iter =
+                LinkedListIter::new(NonNull::new(adapter_address.FirstUnicastAddress));
+
+            let ip_addresses = unicast_addresses_iter
+                .filter_map(|unicast_address| {
+                    let socket_address = NonNull::new(unicast_address.Address.lpSockaddr)?;
+                    get_ip_address_from_socket_address(socket_address)
+                })
+                .collect();
+
+            (get_friendly_name(adapter_address), ip_addresses)
+        })
+        .collect();
+
+    Ok(adapters)
+}
+
+/// Counts the addresses of the system's non-loopback adapters belonging to the
+/// `AF_INET` and `AF_INET6` families respectively.
+///
+/// Adapters are walked once and their friendly names are never decoded.
+pub fn address_family_counts() -> Result<(usize, usize), Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_UNSPEC, 0) {
+        Ok(adapter_addresses) => adapter_addresses,
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => return Ok((0, 0)),
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
+
+    let mut counts = (0, 0);
+
+    for adapter_address in LinkedListIter::new(Some(adapter_addresses.ptr)) {
+        if adapter_address.IfType == IF_TYPE_SOFTWARE_LOOPBACK {
+            continue;
+        }
+
+        for unicast_address in
+            LinkedListIter::new(NonNull::new(adapter_address.FirstUnicastAddress))
+        {
+            match NonNull::new(unicast_address.Address.lpSockaddr)
+                .and_then(get_ip_address_from_socket_address)
+            {
+                Some(IpAddr::V4(_)) => counts.0 += 1,
+                Some(IpAddr::V6(_)) => counts.1 += 1,
+                None => {}
+            }
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Retrieves the addresses of the adapter with the given interface index,
+/// without matching on its friendly name.
+///
+/// Both the IPv4 (`IfIndex`) and IPv6 (`Ipv6IfIndex`) interface indexes of an
+/// adapter are matched. An empty `Vec` is returned when no adapter with the
+/// given index holds an address.
+///
+/// # Example
+///
+/// ```
+/// use local_ip_address::addresses_for_index;
+///
+/// for ip_address in addresses_for_index(1).unwrap() {
+///     println!("{:?}", ip_address);
+/// }
+/// ```
+pub fn addresses_for_index(index: u32) -> Result<Vec<IpAddr>, Error> {
+    let adapter_addresses = match get_adapter_addresses(AF_UNSPEC, 0) {
+        Ok(adapter_addresses) => adapter_addresses,
+        Err(ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA) => return Ok(Vec::new()),
+        Err(error_code) => return Err(win32_error(error_code)),
+    };
+    let adapter_addresses_iter = LinkedListIter::new(Some(adapter_addresses.ptr));
+
+    let ip_addresses = adapter_addresses_iter
+        .filter(|adapter_address| {
+            let interface_index = unsafe { adapter_address.Anonymous1.Anonymous.IfIndex };
+            interface_index == index || adapter_address.Ipv6IfIndex == index
+        })
+        .flat_map(|adapter_address| {
+            let unicast_addresses_iter =
+                LinkedListIter::new(NonNull::new(adapter_address.FirstUnicastAddress));
+
             unicast_addresses_iter.filter_map(|unicast_address| {
                 let socket_address = NonNull::new(unicast_address.Address.lpSockaddr)?;
                 get_ip_address_from_socket_address(socket_address)
-                    .map(|ip_address| (String::from_utf16_lossy(friendly_name), ip_address))
             })
         })
         .collect();
 
-    Ok(network_interfaces)
+    Ok(ip_addresses)
+}
+
+/// Retrieves the index of the adapter with the given friendly name.
+///
+/// The IPv6 interface index is preferred given that it's the one used as scope id
+/// for IPv6 addresses, the IPv4 interface index is used if IPv6 is not enabled
+/// on the adapter.
+pub(crate) fn interface_index(name: &str) -> Result<u32, Error> {
+    let adapter_addresses = get_adapter_addresses(AF_UNSPEC, 0).map_err(|error| match error {
+        ERROR_ADDRESS_NOT_ASSOCIATED | ERROR_NO_DATA => {
+            Error::InterfaceNotFound(String::from(name))
+        }
+        error_code => win32_error(error_code),
+    })?;
+
+    LinkedListIter::new(Some(adapter_addresses.ptr))
+        .find(|adapter_address| {
+            crate::same_interface_name(&get_friendly_name(adapter_address), name)
+        })
+        .map(|adapter_address| {
+            if adapter_address.Ipv6IfIndex != 0 {
+                adapter_address.Ipv6IfIndex
+            } else {
+                unsafe { adapter_address.Anonymous1.Anonymous.IfIndex }
+            }
+        })
+        .ok_or_else(|| Error::InterfaceNotFound(String::from(name)))
+}
+
+/// Retrieves the MAC address of an adapter from its physical address
+fn get_adapter_mac(adapter_address: &IP_ADAPTER_ADDRESSES_LH) -> Option<[u8; 6]> {
+    let len = usize::try_from(adapter_address.PhysicalAddressLength)
+        .unwrap_or(0)
+        .min(adapter_address.PhysicalAddress.len());
+
+    mac_from_bytes(&adapter_address.PhysicalAddress[..len])
+}
+
+/// Determines the medium of an adapter from its interface type
+fn get_adapter_kind(adapter_address: &IP_ADAPTER_ADDRESSES_LH) -> InterfaceKind {
+    match adapter_address.IfType {
+        IF_TYPE_ETHERNET_CSMACD => InterfaceKind::Ethernet,
+        IF_TYPE_IEEE80211 => InterfaceKind::Wireless,
+        IF_TYPE_SOFTWARE_LOOPBACK => InterfaceKind::Loopback,
+        IF_TYPE_TUNNEL => InterfaceKind::Tunnel,
+        _ => InterfaceKind::Other,
+    }
+}
+
+/// Builds the operational flags of an adapter, Windows doesn't distinguish an
+/// administratively up adapter from a running one, both flags are set when
+/// `OperStatus` is `IfOperStatusUp`. Ethernet and wireless adapters are
+/// considered broadcast capable, while PPP and tunnel adapters are considered
+/// point-to-point links.
+fn get_adapter_flags(adapter_address: &IP_ADAPTER_ADDRESSES_LH) -> InterfaceFlags {
+    let mut flags = InterfaceFlags::empty();
+
+    if adapter_address.OperStatus == IfOperStatusUp {
+        flags |= InterfaceFlags::UP | InterfaceFlags::RUNNING;
+    }
+
+    match adapter_address.IfType {
+        IF_TYPE_ETHERNET_CSMACD | IF_TYPE_IEEE80211 => flags |= InterfaceFlags::BROADCAST,
+        IF_TYPE_PPP | IF_TYPE_TUNNEL => flags |= InterfaceFlags::POINTOPOINT,
+        _ => {}
+    }
+
+    if is_multicast_adapter(adapter_address) {
+        flags |= InterfaceFlags::MULTICAST;
+    }
+
+    flags
+}
+
+/// Determines if an adapter supports multicast, loopback adapters are not
+/// considered multicast capable
+fn is_multicast_adapter(adapter_address: &IP_ADAPTER_ADDRESSES_LH) -> bool {
+    let flags = unsafe { adapter_address.Anonymous2.Flags };
+    adapter_address.IfType != IF_TYPE_SOFTWARE_LOOPBACK && flags & IP_ADAPTER_NO_MULTICAST == 0
+}
+
+/// Retrieves the friendly name of an adapter, invalid UTF-16 sequences are replaced
+/// with `U+FFFD REPLACEMENT CHARACTER`
+fn get_friendly_name(adapter_address: &IP_ADAPTER_ADDRESSES_LH) -> String {
+    let friendly_name = unsafe {
+        #[allow(unused_unsafe)]
+        // SAFETY: This is basically how `wcslen` works under the hood. `wcslen` is unsafe because the pointer
+        // is not checked for null and if there is no null-terminating character, it will run forever.
+        // Therefore, safety relies on the operating sysytem always returning a valid string.
+        let len = unsafe {
+            let mut ptr = adapter_address.FriendlyName;
+            while *ptr != 0 {
+                ptr = ptr.offset(1);
+            }
+            // the offset is never negative as `ptr` only moves forward
+            usize::try_from(ptr.offset_from(adapter_address.FriendlyName)).unwrap_or(0)
+        };
+
+        slice::from_raw_parts(adapter_address.FriendlyName, len)
+    };
+
+    String::from_utf16_lossy(friendly_name)
+}
+
+/// Determines if an unicast address is the primary address of its adapter
+fn is_primary_unicast_address(unicast_address: &IP_ADAPTER_UNICAST_ADDRESS_LH) -> bool {
+    let flags = unsafe { unicast_address.Anonymous.Anonymous.Flags };
+
+    (flags & IP_ADAPTER_ADDRESS_DNS_ELIGIBLE) != 0 && (flags & IP_ADAPTER_ADDRESS_TRANSIENT) == 0
 }
 
 /// The [GetIpForwardTable][GetIpForwardTable] function retrieves the IPv4 routing table.
 ///
 /// [GetIpForwardTable]: https://docs.microsoft.com/en-us/windows/win32/api/iphlpapi/nf-iphlpapi-getipforwardtable
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", fields(routes = tracing::field::Empty, retries = tracing::field::Empty))
+)]
 fn get_ip_forward_table(order: BOOL) -> Result<ReadonlyResource<MIB_IPFORWARDTABLE>, WIN32_ERROR> {
     // The minimum size of a routing table.
     const INITIAL_BUFFER_SIZE: u32 = mem::size_of::<MIB_IPFORWARDTABLE>() as u32;
 
     let mut size = INITIAL_BUFFER_SIZE;
+    #[cfg(feature = "tracing")]
+    let mut retries: u32 = 0;
 
     loop {
         let ip_forward_table =
-            ReadonlyResource::new(size.try_into().unwrap()).ok_or(ERROR_NOT_ENOUGH_MEMORY)?;
+            ReadonlyResource::new(size.try_into().map_err(|_| ERROR_NOT_ENOUGH_MEMORY)?)
+                .ok_or(ERROR_NOT_ENOUGH_MEMORY)?;
 
         let result = unsafe { GetIpForwardTable(ip_forward_table.ptr.as_ptr(), &mut size, order) };
 
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("retries", retries);
+
         break match result {
-            ERROR_SUCCESS => Ok(ip_forward_table),
-            ERROR_INSUFFICIENT_BUFFER => continue,
-            #[cfg(debug_assertions)]
-            ERROR_INVALID_PARAMETER => unreachable!(),
+            ERROR_SUCCESS => {
+                #[cfg(feature = "tracing")]
+                tracing::Span::current().record("routes", ip_forward_table.dwNumEntries);
+
+                Ok(ip_forward_table)
+            }
+            ERROR_INSUFFICIENT_BUFFER => {
+                #[cfg(feature = "tracing")]
+                {
+                    retries += 1;
+                }
+
+                continue;
+            }
             ERROR_NO_DATA => Err(ERROR_NO_DATA),
             ERROR_NOT_SUPPORTED => Err(ERROR_NOT_SUPPORTED),
             error => Err(error),
@@ -170,6 +1193,24 @@ fn get_ip_forward_table(order: BOOL) -> Result<ReadonlyResource<MIB_IPFORWARDTAB
     }
 }
 
+/// The [GetIpForwardTable2][GetIpForwardTable2] function retrieves the IP route entries for the given family.
+///
+/// [GetIpForwardTable2]: https://learn.microsoft.com/en-us/windows/win32/api/netioapi/nf-netioapi-getipforwardtable2
+fn get_ip_forward_table2(
+    family: ADDRESS_FAMILY,
+) -> Result<MibTable<MIB_IPFORWARD_TABLE2>, WIN32_ERROR> {
+    let mut table = ptr::null_mut();
+
+    let result = unsafe { GetIpForwardTable2(family, &mut table) };
+
+    match result {
+        ERROR_SUCCESS => NonNull::new(table)
+            .map(|ptr| MibTable { ptr })
+            .ok_or(ERROR_NOT_ENOUGH_MEMORY),
+        error => Err(error),
+    }
+}
+
 /// The [GetAdaptersAddresses][GetAdaptersAddresses] function retrieves the addresses associated with the adapters on
 /// the local computer.
 ///
@@ -177,15 +1218,35 @@ fn get_ip_forward_table(order: BOOL) -> Result<ReadonlyResource<MIB_IPFORWARDTAB
 fn get_adapter_addresses(
     family: ADDRESS_FAMILY,
     flags: GET_ADAPTERS_ADDRESSES_FLAGS,
+) -> Result<ReadonlyResource<IP_ADAPTER_ADDRESSES_LH>, WIN32_ERROR> {
+    get_adapter_addresses_with_size_hint(family, flags, None)
+}
+
+/// Retrieves adapter addresses just as [`get_adapter_addresses`] does, starting
+/// with a buffer of `size_hint` bytes when given.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", fields(adapters = tracing::field::Empty, retries = tracing::field::Empty))
+)]
+fn get_adapter_addresses_with_size_hint(
+    family: ADDRESS_FAMILY,
+    flags: GET_ADAPTERS_ADDRESSES_FLAGS,
+    size_hint: Option<u32>,
 ) -> Result<ReadonlyResource<IP_ADAPTER_ADDRESSES_LH>, WIN32_ERROR> {
     // The recommended buffer size is 15kb.
     const INITIAL_BUFFER_SIZE: u32 = 15000;
 
-    let mut size: u32 = INITIAL_BUFFER_SIZE;
+    // An empty buffer can't be allocated, a zero hint is ignored
+    let mut size: u32 = size_hint
+        .filter(|size_hint| *size_hint > 0)
+        .unwrap_or(INITIAL_BUFFER_SIZE);
+    #[cfg(feature = "tracing")]
+    let mut retries: u32 = 0;
 
     loop {
         let adapter_addresses =
-            ReadonlyResource::new(size.try_into().unwrap()).ok_or(ERROR_NOT_ENOUGH_MEMORY)?;
+            ReadonlyResource::new(size.try_into().map_err(|_| ERROR_NOT_ENOUGH_MEMORY)?)
+                .ok_or(ERROR_NOT_ENOUGH_MEMORY)?;
 
         let result = unsafe {
             GetAdaptersAddresses(
@@ -197,11 +1258,27 @@ fn get_adapter_addresses(
             )
         };
 
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("retries", retries);
+
         break match result {
-            ERROR_SUCCESS => Ok(adapter_addresses),
-            ERROR_BUFFER_OVERFLOW => continue,
-            #[cfg(debug_assertions)]
-            ERROR_INVALID_PARAMETER => unreachable!(),
+            ERROR_SUCCESS => {
+                #[cfg(feature = "tracing")]
+                tracing::Span::current().record(
+                    "adapters",
+                    LinkedListIter::new(Some(adapter_addresses.ptr)).count(),
+                );
+
+                Ok(adapter_addresses)
+            }
+            ERROR_BUFFER_OVERFLOW => {
+                #[cfg(feature = "tracing")]
+                {
+                    retries += 1;
+                }
+
+                continue;
+            }
             ERROR_ADDRESS_NOT_ASSOCIATED => Err(ERROR_ADDRESS_NOT_ASSOCIATED),
             ERROR_NOT_ENOUGH_MEMORY => Err(ERROR_NOT_ENOUGH_MEMORY),
             ERROR_NO_DATA => Err(ERROR_NO_DATA),
@@ -210,9 +1287,23 @@ fn get_adapter_addresses(
     }
 }
 
+/// Retrieves the scope id (`sin6_scope_id`) of an `AF_INET6` socket address,
+/// `None` for other families or when the scope id is not set
+fn get_scope_id(socket_address: NonNull<SOCKADDR>) -> Option<u32> {
+    if unsafe { socket_address.as_ref().sa_family } != AF_INET6 {
+        return None;
+    }
+
+    let socket_address = unsafe { socket_address.cast::<SOCKADDR_IN6>().as_ref() };
+    let scope_id = unsafe { socket_address.Anonymous.sin6_scope_id };
+
+    Some(scope_id).filter(|scope_id| *scope_id != 0)
+}
+
 /// Converts a Windows socket address to an ip address.
 fn get_ip_address_from_socket_address(socket_address: NonNull<SOCKADDR>) -> Option<IpAddr> {
-    let socket_address_family = u32::from(unsafe { socket_address.as_ref().sa_family }) as u16;
+    // `sa_family` is an `ADDRESS_FAMILY` (`u16`), the same type as `AF_INET` and `AF_INET6`
+    let socket_address_family: ADDRESS_FAMILY = unsafe { socket_address.as_ref().sa_family };
 
     if socket_address_family == AF_INET {
         let socket_address = unsafe { socket_address.cast::<SOCKADDR_IN>().as_ref() };
@@ -229,6 +1320,15 @@ fn get_ip_address_from_socket_address(socket_address: NonNull<SOCKADDR>) -> Opti
     }
 }
 
+/// Builds an `Error::Win32` from a Windows API error code along with its
+/// localized error message.
+fn win32_error(error_code: WIN32_ERROR) -> Error {
+    Error::Win32 {
+        code: error_code,
+        message: format_error_code(error_code),
+    }
+}
+
 /// Formats a Windows API error code to a localized error message.
 // Based on the example in https://docs.microsoft.com/en-us/globalization/localizability/win32-formatmessage.
 fn format_error_code(error_code: WIN32_ERROR) -> String {
@@ -246,14 +1346,16 @@ fn format_error_code(error_code: WIN32_ERROR) -> String {
         )
     };
 
-    debug_assert!(
-        len > 0,
-        "Retrieving static error message from the OS for error code {} failed with error code {}.",
-        error_code,
-        unsafe { GetLastError() }
-    );
+    if len == 0 || wide_ptr.is_null() {
+        return format!(
+            "Retrieving static error message from the OS for error code {} failed with error code {}.",
+            error_code,
+            unsafe { GetLastError() }
+        );
+    }
 
-    let slice = unsafe { slice::from_raw_parts(wide_ptr, len.try_into().unwrap()) };
+    let len = usize::try_from(len).unwrap_or(0);
+    let slice = unsafe { slice::from_raw_parts(wide_ptr, len) };
     let error_message = String::from_utf16_lossy(slice);
 
     unsafe {
@@ -271,6 +1373,13 @@ struct ReadonlyResource<T> {
     layout: Layout,
 }
 
+/// Wrapper type around a table allocated by the IP Helper API.
+///
+/// This type ensures that the table is released through `FreeMibTable` automatically.
+struct MibTable<T> {
+    ptr: NonNull<T>,
+}
+
 /// A trait to allow low level linked list data structures to be used as Rust iterators.
 ///
 /// The networking data structures often contain linked lists, which (unfortunately) are a separate types with
@@ -311,6 +1420,22 @@ impl<T> Drop for ReadonlyResource<T> {
     }
 }
 
+impl<T> Deref for MibTable<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> Drop for MibTable<T> {
+    fn drop(&mut self) {
+        unsafe {
+            FreeMibTable(self.ptr.as_ptr().cast());
+        }
+    }
+}
+
 impl LinkedListIterator for IP_ADAPTER_ADDRESSES_LH {
     fn next(&self) -> Option<NonNull<Self>> {
         NonNull::new(self.Next)
@@ -323,6 +1448,18 @@ impl LinkedListIterator for IP_ADAPTER_UNICAST_ADDRESS_LH {
     }
 }
 
+impl LinkedListIterator for IP_ADAPTER_GATEWAY_ADDRESS_LH {
+    fn next(&self) -> Option<NonNull<Self>> {
+        NonNull::new(self.Next)
+    }
+}
+
+impl LinkedListIterator for IP_ADAPTER_DNS_SERVER_ADDRESS_XP {
+    fn next(&self) -> Option<NonNull<Self>> {
+        NonNull::new(self.Next)
+    }
+}
+
 impl<'linked_list, T: LinkedListIterator> LinkedListIter<'linked_list, T> {
     /// Creates a new [LinkedListIter] from a pointer to the head of the linked list.
     pub fn new(head: Option<NonNull<T>>) -> Self {
@@ -343,3 +1480,26 @@ impl<'linked_list, T: LinkedListIterator> Iterator for LinkedListIter<'linked_li
         Some(item)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::rank_default_routes;
+
+    #[test]
+    fn rank_default_routes_by_route_and_interface_metric() {
+        // Default routes commonly have a zero route metric, the interface metric
+        // decides which one is preferred
+        let routes = vec![
+            (0, Some(35), true, 12),
+            (0, Some(25), true, 7),
+            (0, Some(25), false, 7),
+            (256, Some(5), false, 12),
+            (0, None, true, 3),
+        ];
+
+        assert_eq!(
+            rank_default_routes(routes),
+            vec![(false, 7), (true, 7), (true, 12), (false, 12), (true, 3)]
+        );
+    }
+}